@@ -0,0 +1,211 @@
+use std::fmt;
+
+use reqwest::header::ACCEPT;
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::guard::TestbedGuard;
+use crate::types::{
+    BinlogOffset, ConnectorOffsets, ConnectorStatus, ErrorBody, EventPage, GenerateRequest, GenerateSummary, SqlRequest,
+    SqlResult, StartPosition, TestBed, TestBedOptions,
+};
+
+/// The testbed server, as started with `cargo run`
+pub const TESTBED_URL: &str = "http://localhost:8000";
+
+/// An error from the testbed server
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request couldn't be sent or the response couldn't be read
+    Http(reqwest::Error),
+    /// The server responded with an error status
+    Api { status: StatusCode, body: ErrorBody },
+}
+
+impl ClientError {
+    /// True when the server has no such testbed
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ClientError::Api { status, .. } if *status == StatusCode::NOT_FOUND)
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Http(e) => write!(f, "testbed request failed: {e}"),
+            ClientError::Api { status, body } => write!(f, "testbed server responded with {status}: {}", body.error),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Http(e) => Some(e),
+            ClientError::Api { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        ClientError::Http(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// A client for the testbed server's HTTP API
+#[derive(Debug, Clone)]
+pub struct TestbedClient {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl Default for TestbedClient {
+    /// A client for the testbed server on its default port
+    fn default() -> Self {
+        TestbedClient::new(TESTBED_URL)
+    }
+}
+
+impl TestbedClient {
+    /// Creates a client for the server at the URL, i.e. `http://localhost:8000`
+    pub fn new(url: &str) -> Self {
+        TestbedClient {
+            url: url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Creates a testbed named `<name>_<random number>`
+    pub async fn create(&self, name: &str, options: &TestBedOptions) -> Result<TestBed> {
+        self.send_json(self.request(Method::POST, &format!("/testbed/{name}")).json(options))
+            .await
+    }
+
+    /// Creates a testbed like `create`, which is deleted when the returned guard is dropped
+    pub async fn create_guarded(&self, name: &str, options: &TestBedOptions) -> Result<TestbedGuard> {
+        let testbed = self.create(name, options).await?;
+        Ok(TestbedGuard::new(self.clone(), testbed))
+    }
+
+    pub async fn get(&self, name: &str) -> Result<TestBed> {
+        self.send_json(self.request(Method::GET, &format!("/testbed/{name}"))).await
+    }
+
+    /// Returns every live testbed, sorted by name
+    pub async fn list(&self) -> Result<Vec<TestBed>> {
+        self.send_json(self.request(Method::GET, "/testbeds")).await
+    }
+
+    /// Deletes the testbed's connector, database, topics and subjects
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        self.send(self.request(Method::DELETE, &format!("/testbed/{name}"))).await?;
+        Ok(())
+    }
+
+    /// Runs the SQL against the testbed database
+    pub async fn execute(&self, name: &str, sql: &str) -> Result<SqlResult> {
+        let request = SqlRequest { sql: sql.to_string() };
+        self.send_json(self.request(Method::POST, &format!("/testbed/{name}/sql")).json(&request))
+            .await
+    }
+
+    /// Writes random rows to the testbed's tables, returning once every operation has run
+    pub async fn generate(&self, name: &str, request: &GenerateRequest) -> Result<GenerateSummary> {
+        self.send_json(self.request(Method::POST, &format!("/testbed/{name}/generate")).json(request))
+            .await
+    }
+
+    /// Reads a page of up to `limit` decoded events from the topic of the testbed's table. Pass the
+    /// page's `next`, parsed as a `StartPosition`, to read the following page
+    pub async fn events(&self, name: &str, table: &str, from: &StartPosition, limit: Option<usize>) -> Result<EventPage> {
+        let mut query = vec![("table", table.to_string())];
+        query.push(match from {
+            StartPosition::Earliest => ("from", "earliest".to_string()),
+            StartPosition::Latest => ("from", "latest".to_string()),
+            StartPosition::Offset(offset) => ("from", offset.to_string()),
+            StartPosition::Partitions(offsets) => (
+                "from",
+                offsets.iter().map(|(p, o)| format!("{p}:{o}")).collect::<Vec<_>>().join(","),
+            ),
+            StartPosition::Timestamp(timestamp) => ("timestamp", timestamp.to_string()),
+        });
+        if let Some(limit) = limit {
+            query.push(("limit", limit.to_string()));
+        }
+
+        self.send_json(self.request(Method::GET, &format!("/testbed/{name}/events")).query(&query))
+            .await
+    }
+
+    /// Returns the state of the testbed's connector and its tasks
+    pub async fn connector_status(&self, name: &str) -> Result<ConnectorStatus> {
+        self.send_json(self.request(Method::GET, &format!("/testbed/{name}/connector"))).await
+    }
+
+    pub async fn pause_connector(&self, name: &str) -> Result<ConnectorStatus> {
+        self.send_json(self.request(Method::POST, &format!("/testbed/{name}/connector/pause"))).await
+    }
+
+    pub async fn resume_connector(&self, name: &str) -> Result<ConnectorStatus> {
+        self.send_json(self.request(Method::POST, &format!("/testbed/{name}/connector/resume"))).await
+    }
+
+    /// Restarts the testbed's connector; with `include_tasks` its tasks are restarted too, and with
+    /// `only_failed` only the instances that have failed are restarted
+    pub async fn restart_connector(&self, name: &str, include_tasks: bool, only_failed: bool) -> Result<ConnectorStatus> {
+        let request = self
+            .request(Method::POST, &format!("/testbed/{name}/connector/restart"))
+            .query(&[("includeTasks", include_tasks), ("onlyFailed", only_failed)]);
+        self.send_json(request).await
+    }
+
+    /// Returns the binlog position of the testbed's connector
+    pub async fn connector_offsets(&self, name: &str) -> Result<ConnectorOffsets> {
+        self.send_json(self.request(Method::GET, &format!("/testbed/{name}/offsets"))).await
+    }
+
+    /// Moves the testbed's connector to the binlog position
+    pub async fn seek_connector(&self, name: &str, offset: &BinlogOffset) -> Result<ConnectorOffsets> {
+        self.send_json(self.request(Method::PATCH, &format!("/testbed/{name}/offsets")).json(offset))
+            .await
+    }
+
+    /// Clears the offsets of the testbed's connector, so it snapshots the database again
+    pub async fn reset_connector_offsets(&self, name: &str) -> Result<ConnectorOffsets> {
+        self.send_json(self.request(Method::DELETE, &format!("/testbed/{name}/offsets"))).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.url, path))
+            .header(ACCEPT, "application/json")
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response> {
+        let response = request.send().await?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let text = response.text().await.unwrap_or_default();
+        let body = serde_json::from_str(&text).unwrap_or(ErrorBody {
+            error: text,
+            config_errors: vec![],
+        });
+
+        Err(ClientError::Api { status, body })
+    }
+
+    async fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        Ok(self.send(request).await?.json().await?)
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An event from a Debezium MySQL connector's topic
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<T> {
+    /// A row was read, created, updated or deleted
+    Change(ChangeEvent<T>),
+    /// The record with a `null` value that follows a delete, so compaction can drop the key
+    Tombstone,
+    /// A DDL statement, from the schema change topic
+    SchemaChange(SchemaChange),
+}
+
+/// A change to a row, with the row deserialized into `T`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent<T> {
+    /// The row before the change; `None` for creates and reads
+    pub before: Option<T>,
+    /// The row after the change; `None` for deletes
+    pub after: Option<T>,
+    pub op: Op,
+    pub source: Source,
+    /// When the connector processed the change, in milliseconds since the epoch
+    pub ts_ms: Option<i64>,
+    /// Set when the connector has `provide.transaction.metadata` on
+    #[serde(default)]
+    pub transaction: Option<Transaction>,
+}
+
+/// The operation of a change event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Op {
+    #[serde(rename = "c")]
+    Create,
+    #[serde(rename = "u")]
+    Update,
+    #[serde(rename = "d")]
+    Delete,
+    /// A row read by a snapshot
+    #[serde(rename = "r")]
+    Read,
+    #[serde(rename = "t")]
+    Truncate,
+    #[serde(rename = "m")]
+    Message,
+}
+
+/// Where in MySQL a change came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Source {
+    pub version: String,
+    pub connector: String,
+    /// The topic prefix of the connector
+    pub name: String,
+    /// When the change was made in the database, in milliseconds since the epoch
+    pub ts_ms: i64,
+    /// `true`, `last` or `false`, or `incremental` during an incremental snapshot
+    #[serde(default)]
+    pub snapshot: Option<String>,
+    pub db: String,
+    #[serde(default)]
+    pub table: Option<String>,
+    pub server_id: i64,
+    #[serde(default)]
+    pub gtid: Option<String>,
+    /// The binlog file and the position in it of the change's event
+    pub file: String,
+    pub pos: i64,
+    /// The row within the event
+    pub row: i32,
+    #[serde(default)]
+    pub thread: Option<i64>,
+    /// The statement that made the change, when the server has `binlog_rows_query_log_events` on
+    #[serde(default)]
+    pub query: Option<String>,
+}
+
+/// The transaction a change was part of
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transaction {
+    pub id: String,
+    pub total_order: i64,
+    pub data_collection_order: i64,
+}
+
+/// A DDL statement, as written to the schema change topic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaChange {
+    pub source: Source,
+    #[serde(default)]
+    pub ts_ms: Option<i64>,
+    #[serde(default)]
+    pub database_name: Option<String>,
+    #[serde(default)]
+    pub schema_name: Option<String>,
+    #[serde(default)]
+    pub ddl: Option<String>,
+    /// The structure of each changed table, as Debezium describes it
+    #[serde(default)]
+    pub table_changes: Vec<Value>,
+}
+
+/// An event that couldn't be deserialized
+#[derive(Debug)]
+pub enum EventError {
+    Json(serde_json::Error),
+    #[cfg(feature = "avro")]
+    Avro(apache_avro::Error),
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventError::Json(e) => write!(f, "could not deserialize JSON event: {e}"),
+            #[cfg(feature = "avro")]
+            EventError::Avro(e) => write!(f, "could not deserialize Avro event: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for EventError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EventError::Json(e) => Some(e),
+            #[cfg(feature = "avro")]
+            EventError::Avro(e) => Some(e),
+        }
+    }
+}
+
+impl From<serde_json::Error> for EventError {
+    fn from(e: serde_json::Error) -> Self {
+        EventError::Json(e)
+    }
+}
+
+#[cfg(feature = "avro")]
+impl From<apache_avro::Error> for EventError {
+    fn from(e: apache_avro::Error) -> Self {
+        EventError::Avro(e)
+    }
+}
+
+impl<T: DeserializeOwned> Event<T> {
+    /// Deserializes a record value written by the `JsonConverter`, with or without schemas,
+    /// or one returned by the testbed's events endpoint. `None` is a tombstone
+    pub fn from_json(payload: Option<&[u8]>) -> Result<Self, EventError> {
+        match payload {
+            Some(payload) => Event::from_json_value(serde_json::from_slice(payload)?),
+            None => Ok(Event::Tombstone),
+        }
+    }
+
+    /// Deserializes a record value already parsed as JSON
+    pub fn from_json_value(value: Value) -> Result<Self, EventError> {
+        // with schemas.enable the event is wrapped in {"schema": ..., "payload": ...}
+        let value = match value {
+            Value::Object(mut object) if object.contains_key("schema") && object.contains_key("payload") => {
+                object.remove("payload").unwrap_or(Value::Null)
+            }
+            value => value,
+        };
+
+        if value.is_null() {
+            Ok(Event::Tombstone)
+        } else if value.get("ddl").is_some() || value.get("tableChanges").is_some() {
+            Ok(Event::SchemaChange(serde_json::from_value(value)?))
+        } else {
+            Ok(Event::Change(serde_json::from_value(value)?))
+        }
+    }
+
+    /// Deserializes a record value decoded by an Avro decoder. `None` is a tombstone
+    #[cfg(feature = "avro")]
+    pub fn from_avro(value: Option<&apache_avro::types::Value>) -> Result<Self, EventError> {
+        use apache_avro::types::Value as AvroValue;
+
+        let value = match value {
+            None | Some(AvroValue::Null) => return Ok(Event::Tombstone),
+            Some(AvroValue::Union(_, inner)) if **inner == AvroValue::Null => return Ok(Event::Tombstone),
+            Some(value) => value,
+        };
+
+        let is_schema_change = match value {
+            AvroValue::Record(fields) => fields.iter().any(|(name, _)| name == "ddl" || name == "tableChanges"),
+            _ => false,
+        };
+
+        if is_schema_change {
+            Ok(Event::SchemaChange(apache_avro::from_value(value)?))
+        } else {
+            Ok(Event::Change(apache_avro::from_value(value)?))
+        }
+    }
+}
+
+impl<T> Event<T> {
+    /// Returns the change, if this is a change event
+    pub fn change(&self) -> Option<&ChangeEvent<T>> {
+        match self {
+            Event::Change(change) => Some(change),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct Example {
+    id: i32,
+}
+
+#[cfg(test)]
+const SOURCE: &str = r#"{"version":"2.4.2.Final","connector":"mysql","name":"demo_1","ts_ms":1718000000000,
+    "snapshot":"false","db":"demo_1","table":"example","server_id":1,"gtid":null,
+    "file":"mysql-bin.000003","pos":1254,"row":0,"thread":12,"query":null}"#;
+
+#[test]
+fn test_change_event_from_json() {
+    let json = format!(r#"{{"before":null,"after":{{"id":42}},"op":"c","source":{SOURCE},"ts_ms":1718000000123,"transaction":null}}"#);
+
+    let event = Event::<Example>::from_json(Some(json.as_bytes())).unwrap();
+    let change = event.change().unwrap();
+    assert_eq!(change.op, Op::Create);
+    assert_eq!(change.after, Some(Example { id: 42 }));
+    assert_eq!(change.source.file, "mysql-bin.000003");
+    assert_eq!(change.source.pos, 1254);
+
+    // the same event with schemas.enable
+    let wrapped = format!(r#"{{"schema":{{"type":"struct"}},"payload":{json}}}"#);
+    assert_eq!(Event::<Example>::from_json(Some(wrapped.as_bytes())).unwrap(), event);
+}
+
+#[test]
+fn test_tombstone_and_schema_change_from_json() {
+    assert_eq!(Event::<Example>::from_json(None).unwrap(), Event::Tombstone);
+    assert_eq!(Event::<Example>::from_json(Some(b"null")).unwrap(), Event::Tombstone);
+
+    let json = format!(r#"{{"source":{SOURCE},"ts_ms":1718000000123,"databaseName":"demo_1","schemaName":null,
+        "ddl":"ALTER TABLE example ADD COLUMN note VARCHAR(10)","tableChanges":[{{"type":"ALTER","id":"\"demo_1\".\"example\""}}]}}"#);
+    match Event::<Example>::from_json(Some(json.as_bytes())).unwrap() {
+        Event::SchemaChange(change) => {
+            assert_eq!(change.database_name.as_deref(), Some("demo_1"));
+            assert_eq!(change.table_changes.len(), 1);
+        }
+        other => panic!("expected a schema change, got {other:?}"),
+    }
+}
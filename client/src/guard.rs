@@ -0,0 +1,105 @@
+use std::ops::Deref;
+
+use crate::client::{Result, TestbedClient};
+use crate::types::TestBed;
+
+/// Set to `1` or `true` to keep the testbeds of failing tests for inspection, rather than deleting them
+pub const KEEP_FAILED_ENV: &str = "DEBEZIUM_TESTBED_KEEP_FAILED";
+
+/// A testbed that's deleted when the guard is dropped, including when the test panics. Derefs to
+/// the `TestBed`
+#[derive(Debug)]
+pub struct TestbedGuard {
+    client: TestbedClient,
+    testbed: TestBed,
+    keep: bool,
+}
+
+impl TestbedGuard {
+    pub fn new(client: TestbedClient, testbed: TestBed) -> Self {
+        TestbedGuard {
+            client,
+            testbed,
+            keep: false,
+        }
+    }
+
+    pub fn testbed(&self) -> &TestBed {
+        &self.testbed
+    }
+
+    /// The client the testbed was created with
+    pub fn client(&self) -> &TestbedClient {
+        &self.client
+    }
+
+    /// Keeps the testbed once the guard is dropped, returning it
+    pub fn keep(mut self) -> TestBed {
+        self.keep = true;
+        self.testbed.clone()
+    }
+
+    /// Deletes the testbed now, returning the error rather than only logging it
+    pub async fn delete(mut self) -> Result<()> {
+        self.keep = true;
+        self.client.delete(&self.testbed.name).await
+    }
+}
+
+impl Deref for TestbedGuard {
+    type Target = TestBed;
+
+    fn deref(&self) -> &TestBed {
+        &self.testbed
+    }
+}
+
+impl Drop for TestbedGuard {
+    fn drop(&mut self) {
+        let keep_failed = std::env::var(KEEP_FAILED_ENV).ok();
+        if !should_delete(self.keep, std::thread::panicking(), keep_failed.as_deref()) {
+            if !self.keep {
+                eprintln!("Keeping testbed {} of a failed test, as {KEEP_FAILED_ENV} is set", self.testbed.name);
+            }
+            return;
+        }
+
+        // the guard may be dropped on a runtime this thread blocks, so the delete gets its own
+        // thread, runtime and connections
+        let url = self.client.url().to_string();
+        let name = self.testbed.name.clone();
+        let deleted = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            runtime
+                .block_on(TestbedClient::new(&url).delete(&name))
+                .map_err(|e| e.to_string())
+        })
+        .join();
+
+        match deleted {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("Could not delete testbed {}: {e}", self.testbed.name),
+            Err(_) => eprintln!("Could not delete testbed {}: the delete panicked", self.testbed.name),
+        }
+    }
+}
+
+/// A testbed is deleted unless it's kept, or the test failed and failed testbeds are kept
+fn should_delete(kept: bool, panicking: bool, keep_failed: Option<&str>) -> bool {
+    let keep_failed = matches!(keep_failed.map(str::trim), Some("1") | Some("true"));
+    let keep = kept || (panicking && keep_failed);
+    !keep
+}
+
+#[test]
+fn test_should_delete() {
+    assert!(should_delete(false, false, None));
+    assert!(should_delete(false, true, None));
+    assert!(should_delete(false, false, Some("1")));
+    assert!(should_delete(false, true, Some("0")));
+    assert!(!should_delete(false, true, Some("true")));
+    assert!(!should_delete(true, false, None));
+}
@@ -0,0 +1,214 @@
+//! Support for the `#[debezium_test]` attribute
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::ops::Deref;
+use std::time::{Duration, Instant};
+
+use mysql::{Pool, PooledConn};
+use serde_json::{json, Map, Value};
+
+use crate::client::{Result, TestbedClient, TESTBED_URL};
+use crate::guard::TestbedGuard;
+use crate::types::{StartPosition, TestBed, TestBedOptions, TopicEvent};
+
+/// How long `EventReader::wait_for` waits between reads that come back short
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The testbed of a `#[debezium_test]`, with a pool of connections to its database. Derefs to
+/// the `TestBed`; the testbed is deleted when the handle is dropped
+#[derive(Debug)]
+pub struct TestbedHandle {
+    guard: TestbedGuard,
+    pool: Pool,
+}
+
+impl TestbedHandle {
+    /// Creates a testbed named after the test, with the settings of the attribute
+    pub async fn provision(test_name: &str, settings: &[(&str, &str)]) -> std::result::Result<Self, String> {
+        let url = settings.iter().find(|(k, _)| *k == "url").map_or(TESTBED_URL, |(_, v)| v);
+        let name = settings.iter().find(|(k, _)| *k == "name").map_or(test_name, |(_, v)| v);
+        let options = options_from_settings(settings)?;
+
+        let guard = TestbedClient::new(url)
+            .create_guarded(name, &options)
+            .await
+            .map_err(|e| format!("could not create testbed for {test_name}: {e}"))?;
+        let pool = Pool::new(guard.mysql_url.as_str()).map_err(|e| format!("could not connect to {}: {e}", guard.name))?;
+
+        Ok(TestbedHandle { guard, pool })
+    }
+
+    pub fn testbed(&self) -> &TestBed {
+        &self.guard
+    }
+
+    pub fn client(&self) -> &TestbedClient {
+        self.guard.client()
+    }
+
+    pub fn pool(&self) -> &Pool {
+        &self.pool
+    }
+
+    /// Returns a connection to the testbed database
+    pub fn conn(&self) -> PooledConn {
+        self.pool.get_conn().expect("a connection to the testbed database")
+    }
+
+    /// Returns the topic of one of the testbed's tables, after any `reroute` transforms
+    pub fn topic(&self, table: &str) -> String {
+        self.testbed().topic(table)
+    }
+
+    /// Returns a reader for the events of one of the testbed's tables, from the earliest
+    pub fn events(&self, table: &str) -> EventReader {
+        EventReader {
+            client: self.guard.client().clone(),
+            testbed_name: self.guard.name.clone(),
+            table: table.to_string(),
+            next: StartPosition::Earliest,
+        }
+    }
+
+    /// Keeps the testbed once the test ends, returning it
+    pub fn keep(self) -> TestBed {
+        self.guard.keep()
+    }
+}
+
+impl Deref for TestbedHandle {
+    type Target = TestBed;
+
+    fn deref(&self) -> &TestBed {
+        &self.guard
+    }
+}
+
+/// Reads the events of a table's topic page by page through the testbed server
+#[derive(Debug, Clone)]
+pub struct EventReader {
+    client: TestbedClient,
+    testbed_name: String,
+    table: String,
+    next: StartPosition,
+}
+
+impl EventReader {
+    /// Reads the events written since the last read, up to `limit`
+    pub async fn read(&mut self, limit: usize) -> Result<Vec<TopicEvent>> {
+        let page = self
+            .client
+            .events(&self.testbed_name, &self.table, &self.next, Some(limit))
+            .await?;
+        if let Ok(next) = page.next.parse() {
+            self.next = next;
+        }
+
+        Ok(page.events)
+    }
+
+    /// Reads until `count` events have been read or the timeout passes, returning what was read
+    pub async fn wait_for(&mut self, count: usize, timeout: Duration) -> Result<Vec<TopicEvent>> {
+        let deadline = Instant::now() + timeout;
+        let mut events = vec![];
+
+        while events.len() < count {
+            events.extend(self.read(count - events.len()).await?);
+            if events.len() >= count || Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        Ok(events)
+    }
+}
+
+/// What a test can return: nothing, or a `Result` whose error fails the test
+pub trait TestOutput {
+    fn check(self);
+}
+
+impl TestOutput for () {
+    fn check(self) {}
+}
+
+impl<E: Debug> TestOutput for std::result::Result<(), E> {
+    fn check(self) {
+        if let Err(e) = self {
+            panic!("test failed: {e:?}");
+        }
+    }
+}
+
+/// Runs the test on a new runtime with a fresh testbed, which is deleted once the test ends
+pub fn run<F, Fut>(test_name: &str, settings: &[(&str, &str)], test: F)
+where
+    F: FnOnce(TestbedHandle) -> Fut,
+    Fut: Future,
+    Fut::Output: TestOutput,
+{
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("a runtime for the test");
+
+    runtime.block_on(async {
+        let testbed = match TestbedHandle::provision(test_name, settings).await {
+            Ok(testbed) => testbed,
+            Err(e) => panic!("{e}"),
+        };
+        test(testbed).await.check();
+    });
+}
+
+/// Builds the options from the attribute's settings, where `fixtures` is a fixture set and `phase`
+/// when it's applied
+fn options_from_settings(settings: &[(&str, &str)]) -> std::result::Result<TestBedOptions, String> {
+    let mut options = Map::new();
+    let mut phase = None;
+
+    for (key, value) in settings {
+        match *key {
+            "name" | "url" => {}
+            "phase" => phase = Some(*value),
+            "fixtures" => {
+                options.insert("fixtures".to_string(), json!({ "set": value }));
+            }
+            key => {
+                options.insert(key.to_string(), json!(value));
+            }
+        }
+    }
+
+    if let Some(phase) = phase {
+        match options.get_mut("fixtures") {
+            Some(fixtures) => fixtures["phase"] = json!(phase),
+            None => return Err("phase is only valid with fixtures".to_string()),
+        }
+    }
+
+    serde_json::from_value(Value::Object(options)).map_err(|e| format!("invalid debezium_test settings: {e}"))
+}
+
+#[test]
+fn test_options_from_settings() {
+    use crate::types::{Converter, FixturePhase};
+
+    let options = options_from_settings(&[
+        ("name", "orders"),
+        ("server", "5.7"),
+        ("fixtures", "default"),
+        ("phase", "stream"),
+        ("value_converter", "json"),
+    ])
+    .unwrap();
+
+    assert_eq!(options.server.as_deref(), Some("5.7"));
+    assert_eq!(options.fixtures.as_ref().map(|f| (f.set.as_str(), f.phase)), Some(("default", FixturePhase::Stream)));
+    assert_eq!(options.value_converter, Some(Converter::Json));
+
+    assert!(options_from_settings(&[("phase", "stream")]).is_err());
+    assert!(options_from_settings(&[("value_converter", "xml")]).is_err());
+}
@@ -0,0 +1,17 @@
+pub mod client;
+pub mod event;
+pub mod guard;
+pub mod harness;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+pub mod types;
+
+pub use client::{ClientError, TestbedClient, TESTBED_URL};
+pub use event::{ChangeEvent, Event, EventError, Op, SchemaChange, Source, Transaction};
+pub use guard::{TestbedGuard, KEEP_FAILED_ENV};
+pub use harness::{EventReader, TestbedHandle};
+pub use types::{TestBed, TestBedOptions};
+pub use debezium_testbed_macros::debezium_test;
+
+/// The `mysql` crate the pool of a `TestbedHandle` comes from
+pub use mysql;
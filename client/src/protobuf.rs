@@ -0,0 +1,123 @@
+use protofish::context::{Context, Multiplicity};
+use protofish::decode::{MessageValue, PackedArray, Value as ProtoValue};
+use serde_json::{Map, Number, Value};
+
+/// Converts a message decoded with its schema into a JSON object keyed by field name. Repeated
+/// fields are arrays, enums the name of their value, and bytes arrays of byte values. Fields left
+/// at their default value aren't written, so they're missing from the object, as are fields the
+/// schema doesn't know
+pub fn to_json(message: &MessageValue, context: &Context) -> Value {
+    let info = context.resolve_message(message.msg_ref);
+    let mut object = Map::new();
+
+    for field in &message.fields {
+        let Some(schema) = info.get_field(field.number) else {
+            continue;
+        };
+        let values = match &field.value {
+            ProtoValue::Packed(packed) => packed_to_json(packed),
+            ProtoValue::Incomplete(..) | ProtoValue::Unknown(_) => continue,
+            value => vec![value_to_json(value, context)],
+        };
+
+        match schema.multiplicity {
+            Multiplicity::Repeated | Multiplicity::RepeatedPacked => {
+                if let Value::Array(items) = object.entry(schema.name.clone()).or_insert_with(|| Value::Array(vec![])) {
+                    items.extend(values);
+                }
+            }
+            // the last value written for a field wins
+            Multiplicity::Single | Multiplicity::Optional => {
+                if let Some(value) = values.into_iter().last() {
+                    object.insert(schema.name.clone(), value);
+                }
+            }
+        }
+    }
+
+    Value::Object(object)
+}
+
+fn value_to_json(value: &ProtoValue, context: &Context) -> Value {
+    match value {
+        ProtoValue::Double(v) => float_to_json(*v),
+        ProtoValue::Float(v) => float_to_json(f64::from(*v)),
+        ProtoValue::Int32(v) | ProtoValue::SInt32(v) | ProtoValue::SFixed32(v) => Value::from(*v),
+        ProtoValue::Int64(v) | ProtoValue::SInt64(v) | ProtoValue::SFixed64(v) => Value::from(*v),
+        ProtoValue::UInt32(v) | ProtoValue::Fixed32(v) => Value::from(*v),
+        ProtoValue::UInt64(v) | ProtoValue::Fixed64(v) => Value::from(*v),
+        ProtoValue::Bool(v) => Value::Bool(*v),
+        ProtoValue::String(v) => Value::String(v.clone()),
+        ProtoValue::Bytes(v) => Value::from(v.to_vec()),
+        ProtoValue::Enum(v) => match context.resolve_enum(v.enum_ref).get_field_by_value(v.value) {
+            Some(field) => Value::String(field.name.clone()),
+            None => Value::from(v.value),
+        },
+        ProtoValue::Message(message) => to_json(message, context),
+        ProtoValue::Packed(_) | ProtoValue::Incomplete(..) | ProtoValue::Unknown(_) => Value::Null,
+    }
+}
+
+fn packed_to_json(packed: &PackedArray) -> Vec<Value> {
+    match packed {
+        PackedArray::Double(v) => v.iter().map(|v| float_to_json(*v)).collect(),
+        PackedArray::Float(v) => v.iter().map(|v| float_to_json(f64::from(*v))).collect(),
+        PackedArray::Int32(v) | PackedArray::SInt32(v) | PackedArray::SFixed32(v) => v.iter().map(|v| Value::from(*v)).collect(),
+        PackedArray::Int64(v) | PackedArray::SInt64(v) | PackedArray::SFixed64(v) => v.iter().map(|v| Value::from(*v)).collect(),
+        PackedArray::UInt32(v) | PackedArray::Fixed32(v) => v.iter().map(|v| Value::from(*v)).collect(),
+        PackedArray::UInt64(v) | PackedArray::Fixed64(v) => v.iter().map(|v| Value::from(*v)).collect(),
+        PackedArray::Bool(v) => v.iter().map(|v| Value::Bool(*v)).collect(),
+    }
+}
+
+/// NaN and infinity have no JSON number, so they're `null`
+fn float_to_json(v: f64) -> Value {
+    Number::from_f64(v).map_or(Value::Null, Value::Number)
+}
+
+#[test]
+fn test_to_json() {
+    let context = Context::parse([r#"
+        syntax = "proto3";
+        package demo_1.demo_1.example;
+
+        enum Kind { READ = 0; CREATE = 1; }
+
+        message Envelope {
+            message Value {
+                int32 id = 1;
+                string note = 2;
+            }
+            Value before = 1;
+            Value after = 2;
+            string op = 3;
+            int64 ts_ms = 4;
+            repeated string tags = 5;
+            repeated int32 ids = 6;
+            Kind kind = 7;
+        }
+    "#])
+    .unwrap();
+
+    let data = [
+        0x12, 0x06, 0x08, 0x2a, 0x12, 0x02, b'h', b'i', // after: {id: 42, note: "hi"}
+        0x1a, 0x01, b'c', // op: "c"
+        0x20, 0x40, // ts_ms: 64
+        0x2a, 0x01, b'a', 0x2a, 0x01, b'b', // tags: ["a", "b"]
+        0x32, 0x02, 0x01, 0x02, // ids: [1, 2], packed
+        0x38, 0x01, // kind: CREATE
+    ];
+    let message = context.get_message("demo_1.demo_1.example.Envelope").unwrap().decode(&data, &context);
+
+    assert_eq!(
+        to_json(&message, &context),
+        serde_json::json!({
+            "after": { "id": 42, "note": "hi" },
+            "op": "c",
+            "ts_ms": 64,
+            "tags": ["a", "b"],
+            "ids": [1, 2],
+            "kind": "CREATE"
+        })
+    );
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+/// The status of a connector, as returned by `/connectors/<name>/status`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectorStatus {
+    pub name: String,
+    pub connector: ConnectorState,
+    pub tasks: Vec<TaskState>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub connector_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectorState {
+    /// One of `UNASSIGNED`, `RUNNING`, `PAUSED`, `STOPPED`, `FAILED` or `RESTARTING`
+    pub state: String,
+    pub worker_id: String,
+    /// The stack trace of the failure, if the connector has failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskState {
+    pub id: u32,
+    pub state: String,
+    pub worker_id: String,
+    /// The stack trace of the failure, if the task has failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<String>,
+}
+
+impl ConnectorStatus {
+    /// True when the connector and all of its tasks are running
+    pub fn is_running(&self) -> bool {
+        self.connector.state == "RUNNING"
+            && !self.tasks.is_empty()
+            && self.tasks.iter().all(|t| t.state == "RUNNING")
+    }
+}
+
+/// The errors Kafka Connect reported for one property of a connector config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigError {
+    pub property: String,
+    pub errors: Vec<String>,
+}
+
+/// The source offsets of a connector, as returned by `/connectors/<name>/offsets`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConnectorOffsets {
+    pub offsets: Vec<ConnectorOffset>,
+}
+
+impl ConnectorOffsets {
+    /// True while the committed offsets are those of the connector's initial snapshot, which Debezium
+    /// marks with `"snapshot": true` until it has started reading the binlog
+    pub fn in_snapshot(&self) -> bool {
+        self.offsets.iter().any(|o| {
+            o.offset.as_ref().is_some_and(|offset| match &offset["snapshot"] {
+                serde_json::Value::Bool(snapshot) => *snapshot,
+                serde_json::Value::String(snapshot) => snapshot != "false",
+                _ => false,
+            })
+        })
+    }
+}
+
+/// The offset for one source partition. For Debezium MySQL the partition is `{"server": <topic prefix>}`
+/// and the offset holds the binlog `file`, `pos` and `gtids`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectorOffset {
+    pub partition: serde_json::Value,
+    pub offset: Option<serde_json::Value>,
+}
+
+/// The body of an incremental snapshot request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotRequest {
+    /// The tables to snapshot, i.e. `customers`. Names without a database are in the testbed database
+    pub tables: Vec<String>,
+}
+
+/// An `execute-snapshot` signal written to the signal table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotSignal {
+    pub id: String,
+    /// The tables to snapshot, qualified with their database
+    pub tables: Vec<String>,
+    pub progress: SnapshotProgress,
+}
+
+/// The progress of the connector's incremental snapshot, as recorded in its offsets. Offsets are
+/// flushed every second, so this can lag the connector slightly
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotProgress {
+    /// True while an incremental snapshot is running
+    pub running: bool,
+    /// The tables still to be snapshotted, starting with the current one
+    pub tables: Vec<String>,
+    /// The primary key of the last chunk read from the current table, as serialized by Debezium
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_key: Option<String>,
+    /// The largest primary key of the current table when its snapshot started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum_key: Option<String>,
+}
+
+impl SnapshotProgress {
+    pub fn from_offsets(offsets: &ConnectorOffsets) -> SnapshotProgress {
+        let Some(offset) = offsets.offsets.iter().find_map(|o| o.offset.as_ref()) else {
+            return SnapshotProgress::default();
+        };
+        let field = |name: &str| offset.get(name).and_then(|v| v.as_str()).map(String::from);
+
+        let tables = field("incremental_snapshot_collections")
+            .map(|c| parse_collections(&c))
+            .unwrap_or_default();
+
+        SnapshotProgress {
+            running: !tables.is_empty(),
+            tables,
+            last_key: field("incremental_snapshot_primary_key"),
+            maximum_key: field("incremental_snapshot_maximum_key"),
+        }
+    }
+}
+
+/// Debezium 1.x stores the remaining collections as a comma separated list, and 2.x as a JSON
+/// array of `{"incremental_snapshot_collections_id": <table>, ...}`
+fn parse_collections(collections: &str) -> Vec<String> {
+    match serde_json::from_str::<Vec<serde_json::Value>>(collections) {
+        Ok(collections) => collections
+            .iter()
+            .filter_map(|c| c["incremental_snapshot_collections_id"].as_str().map(String::from))
+            .collect(),
+        Err(_) => collections
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(String::from)
+            .collect(),
+    }
+}
+
+#[test]
+fn test_snapshot_progress() {
+    use serde_json::json;
+
+    let offsets: ConnectorOffsets = serde_json::from_value(json!({
+        "offsets": [{
+            "partition": { "server": "demo_1" },
+            "offset": {
+                "file": "mysql-bin.000003",
+                "pos": 1254,
+                "incremental_snapshot_collections": "[{\"incremental_snapshot_collections_id\":\"demo_1.customers\",\"incremental_snapshot_collections_additional_condition\":null},{\"incremental_snapshot_collections_id\":\"demo_1.orders\",\"incremental_snapshot_collections_additional_condition\":null}]",
+                "incremental_snapshot_primary_key": "aced0005757200135b4c6a6176612e6c616e672e4f626a6563743b",
+                "incremental_snapshot_maximum_key": "aced0005757200135b4c6a6176612e6c616e672e4f626a6563743c"
+            }
+        }]
+    }))
+    .unwrap();
+
+    let progress = SnapshotProgress::from_offsets(&offsets);
+    assert!(progress.running);
+    assert_eq!(progress.tables, vec!["demo_1.customers", "demo_1.orders"]);
+    assert!(progress.last_key.is_some());
+
+    assert_eq!(parse_collections("demo_1.customers,demo_1.orders"), vec!["demo_1.customers", "demo_1.orders"]);
+    assert_eq!(SnapshotProgress::from_offsets(&ConnectorOffsets::default()), SnapshotProgress::default());
+}
+
+#[test]
+fn test_in_snapshot() {
+    let offsets = |offset: serde_json::Value| ConnectorOffsets {
+        offsets: vec![ConnectorOffset {
+            partition: serde_json::json!({"server": "demo_1"}),
+            offset: Some(offset),
+        }],
+    };
+
+    assert!(offsets(serde_json::json!({"file": "mysql-bin.000003", "pos": 154, "snapshot": true})).in_snapshot());
+    assert!(offsets(serde_json::json!({"file": "mysql-bin.000003", "pos": 154, "snapshot": "true"})).in_snapshot());
+    assert!(!offsets(serde_json::json!({"file": "mysql-bin.000003", "pos": 154})).in_snapshot());
+    assert!(!ConnectorOffsets::default().in_snapshot());
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// The most operations one generate request may run
+pub const MAX_GENERATE_OPERATIONS: u32 = 100_000;
+
+/// The body of a request to write random rows to a testbed's tables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerateRequest {
+    /// The tables to write to; every table in the testbed by default
+    pub tables: Vec<String>,
+    /// How many inserts, updates and deletes to run in all
+    pub operations: u32,
+    /// Operations per second; as fast as the server takes them when not given
+    pub rate: Option<f64>,
+    /// The share of operations that update a row, from 0 to 1
+    pub update_ratio: f64,
+    /// The share of operations that delete a row, from 0 to 1
+    pub delete_ratio: f64,
+    /// Seeds the random values, so a run can be repeated against the same rows
+    pub seed: Option<u64>,
+}
+
+impl Default for GenerateRequest {
+    fn default() -> Self {
+        GenerateRequest {
+            tables: vec![],
+            operations: 100,
+            rate: None,
+            update_ratio: 0.0,
+            delete_ratio: 0.0,
+            seed: None,
+        }
+    }
+}
+
+impl GenerateRequest {
+    /// Returns what's wrong with the request, if anything
+    pub fn validate(&self) -> Result<(), String> {
+        if self.operations == 0 || self.operations > MAX_GENERATE_OPERATIONS {
+            return Err(format!("operations must be from 1 to {MAX_GENERATE_OPERATIONS}"));
+        }
+        if self.rate.is_some_and(|rate| rate.is_nan() || rate <= 0.0) {
+            return Err("rate must be more than 0".to_string());
+        }
+        let is_ratio = |r: f64| (0.0..=1.0).contains(&r);
+        if !is_ratio(self.update_ratio) || !is_ratio(self.delete_ratio) || self.update_ratio + self.delete_ratio > 1.0 {
+            return Err("update_ratio and delete_ratio must be from 0 to 1, and add up to at most 1".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// What a generate request did
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerateSummary {
+    pub inserted: u64,
+    pub updated: u64,
+    pub deleted: u64,
+    /// Operations MySQL rejected, i.e. a delete of a row other rows still reference
+    pub skipped: u64,
+    pub elapsed_ms: u64,
+}
+
+#[test]
+fn test_validate_generate_request() {
+    assert!(GenerateRequest::default().validate().is_ok());
+
+    let request: GenerateRequest = serde_json::from_str(r#"{"operations": 10, "update_ratio": 0.5, "delete_ratio": 0.25}"#).unwrap();
+    assert!(request.validate().is_ok());
+    assert!(request.tables.is_empty());
+
+    let invalid = [
+        GenerateRequest { operations: 0, ..Default::default() },
+        GenerateRequest { rate: Some(0.0), ..Default::default() },
+        GenerateRequest { update_ratio: 0.75, delete_ratio: 0.5, ..Default::default() },
+        GenerateRequest { delete_ratio: -0.1, ..Default::default() },
+    ];
+    for request in invalid {
+        assert!(request.validate().is_err(), "{request:?}");
+    }
+}
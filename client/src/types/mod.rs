@@ -0,0 +1,53 @@
+//! The types of the testbed server's API, shared by the server and the client so the two can't drift
+
+use serde::{Deserialize, Serialize};
+
+pub mod connector;
+pub mod generate;
+pub mod testbed;
+pub mod topics;
+
+pub use connector::{
+    ConfigError, ConnectorOffset, ConnectorOffsets, ConnectorState, ConnectorStatus, SnapshotProgress, SnapshotRequest,
+    SnapshotSignal, TaskState,
+};
+pub use generate::{GenerateRequest, GenerateSummary, MAX_GENERATE_OPERATIONS};
+pub use testbed::{
+    BinlogOffset, CloneStrategy, Compatibility, Converter, FixtureOptions, FixturePhase, TestBed, TestBedOptions,
+    TopicSettings, Transform,
+};
+pub use topics::{
+    table_topic, EventHeader, EventPage, Schema, StartPosition, SubjectInfo, TopicEvent, TopicInfo, TopicRoute,
+};
+
+/// The JSON body returned when a request fails
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorBody {
+    pub error: String,
+    /// The properties Kafka Connect rejected, for an invalid connector config
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub config_errors: Vec<ConfigError>,
+}
+
+/// SQL to run against a testbed database
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SqlRequest {
+    /// One or more statements separated by `;`
+    pub sql: String,
+}
+
+/// The outcome of running SQL against a testbed database
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SqlResult {
+    /// The rows changed by every statement
+    pub affected_rows: u64,
+    /// The `AUTO_INCREMENT` id of the last insert, if there was one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_insert_id: Option<u64>,
+    /// The columns of the last statement that returned rows
+    #[serde(default)]
+    pub columns: Vec<String>,
+    /// The rows of the last statement that returned rows, with values in the order of `columns`
+    #[serde(default)]
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
@@ -0,0 +1,266 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::types::connector::{ConnectorOffset, ConnectorOffsets};
+use crate::types::topics::{table_topic, TopicRoute};
+
+/// A testbed, as returned when it's created
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestBed {
+    pub name: String,
+    /// The template database the testbed was cloned from
+    pub template: String,
+    /// The version of the MySQL server the testbed database lives on
+    pub server: String,
+    /// The Debezium release running the testbed's connector
+    pub debezium: String,
+    /// URL for connecting from the host to the testbed database
+    pub mysql_url: String,
+    /// The connector's `database.server.id`, unique among live testbeds
+    pub server_id: u32,
+    /// The formats of the keys and values in the testbed's topics
+    pub key_converter: Converter,
+    pub value_converter: Converter,
+    /// How the template database was cloned, and how long it took
+    pub clone_strategy: CloneStrategy,
+    pub clone_ms: u64,
+    /// The connector's `ByLogicalTableRouter` transforms, which rename the topics they match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topic_routes: Vec<TopicRoute>,
+}
+
+impl TestBed {
+    /// Returns the topic of one of the testbed's tables, after any routes
+    pub fn topic(&self, table: &str) -> String {
+        table_topic(&self.name, table, &self.topic_routes)
+    }
+}
+
+/// Options for stamping out a testbed; every field is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TestBedOptions {
+    /// The version of the MySQL server to clone onto, i.e. `5.7`. Defaults to `8.0`
+    pub server: Option<String>,
+    /// The Debezium release to run the connector on, i.e. `1.7`. Defaults to `2.4`, or `2.7` on MySQL 8.4
+    pub debezium: Option<String>,
+    /// How the template database is cloned. Defaults to `dump`
+    pub clone_strategy: CloneStrategy,
+    /// The template in `templates/` to clone. Defaults to `mydb`
+    pub template: Option<String>,
+    /// Fixtures from the template to apply once the database is cloned
+    pub fixtures: Option<FixtureOptions>,
+    /// The format of the keys in the testbed's topics. Defaults to `string`
+    pub key_converter: Option<Converter>,
+    /// The format of the values in the testbed's topics. Defaults to `avro`
+    pub value_converter: Option<Converter>,
+    /// Single Message Transform presets, stacked in order onto the connector
+    pub transforms: Vec<Transform>,
+    /// A binlog position to stream from instead of snapshotting the testbed database
+    pub offset: Option<BinlogOffset>,
+    /// Settings to create the table topics with before the connector starts. Without them,
+    /// the topics are created by the broker with its defaults
+    pub topics: Option<TopicSettings>,
+    /// The compatibility level of the testbed's registry subjects. Defaults to the registry's own
+    pub compatibility: Option<Compatibility>,
+}
+
+/// How a template database is cloned into a testbed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneStrategy {
+    /// Pipes `mysqldump` into `mysql` through a Docker exec in the server's container
+    #[default]
+    Dump,
+    /// Copies the database over a native connection with `CREATE TABLE ... LIKE` and
+    /// `INSERT ... SELECT`, then recreates views, triggers and routines
+    InServer,
+}
+
+/// When fixtures are applied to a cloned testbed database, relative to the connector starting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixturePhase {
+    /// Before the connector is created, so the rows appear as snapshot (`r`) events
+    #[default]
+    Snapshot,
+    /// Once the connector is running and has finished its snapshot, so the rows appear as create (`c`) events
+    Stream,
+}
+
+/// A set of fixtures to apply to a testbed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureOptions {
+    /// The directory in the template's `fixtures/` to apply
+    pub set: String,
+    #[serde(default)]
+    pub phase: FixturePhase,
+}
+
+/// A Kafka Connect converter, which decides the format of a topic's keys or values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Converter {
+    String,
+    /// Avro registered in the Schema Registry
+    Avro,
+    /// JSON with the schema embedded in every message
+    Json,
+    /// JSON registered in the Schema Registry as a JSON Schema
+    JsonSchema,
+    /// Protobuf registered in the Schema Registry
+    Protobuf,
+}
+
+impl Converter {
+    pub fn class(&self) -> &'static str {
+        match self {
+            Converter::String => "org.apache.kafka.connect.storage.StringConverter",
+            Converter::Avro => "io.confluent.connect.avro.AvroConverter",
+            Converter::Json => "org.apache.kafka.connect.json.JsonConverter",
+            Converter::JsonSchema => "io.confluent.connect.json.JsonSchemaConverter",
+            Converter::Protobuf => "io.confluent.connect.protobuf.ProtobufConverter",
+        }
+    }
+
+    /// True when the converter registers schemas in the Schema Registry
+    pub fn uses_registry(&self) -> bool {
+        matches!(self, Converter::Avro | Converter::JsonSchema | Converter::Protobuf)
+    }
+}
+
+/// A Single Message Transform preset. Presets are applied in the order they are given, after any
+/// transforms already in the template's `connector.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Transform {
+    /// Replaces the change event envelope with the row's new state (`ExtractNewRecordState`)
+    Unwrap {
+        /// Drop the tombstones that follow deletes
+        #[serde(default)]
+        drop_tombstones: bool,
+        /// `drop`, `rewrite` or `none`; Debezium's default is `drop`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        delete_handling: Option<String>,
+        /// Metadata fields to add to the record, i.e. `op` or `source.ts_ms`
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        add_fields: Vec<String>,
+    },
+    /// Routes records from topics matching `topic_regex` to `topic_replacement` (`ByLogicalTableRouter`)
+    Reroute {
+        topic_regex: String,
+        topic_replacement: String,
+    },
+    /// Drops records from topics matching `topic_regex`, or with `keep` drops every other record
+    Filter {
+        topic_regex: String,
+        #[serde(default)]
+        keep: bool,
+    },
+    /// Adds a header to every record. The value defaults to the testbed name
+    Header {
+        header: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+    },
+    /// Replaces the given fields of the record value with an empty value or `replacement`.
+    /// Only top level fields can be masked, so this is usually stacked after `unwrap`
+    Mask {
+        fields: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        replacement: Option<String>,
+    },
+}
+
+impl Transform {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::Unwrap { .. } => "unwrap",
+            Transform::Reroute { .. } => "reroute",
+            Transform::Filter { .. } => "filter",
+            Transform::Header { .. } => "header",
+            Transform::Mask { .. } => "mask",
+        }
+    }
+}
+
+/// A position in the binlog for a connector to stream from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BinlogOffset {
+    /// The binlog file, i.e. `mysql-bin.000003`
+    pub file: String,
+    /// The position in the file. Defaults to 4, the first event
+    #[serde(default = "first_position")]
+    pub pos: u64,
+    /// A GTID set; when given the connector skips these transactions rather than seeking to
+    /// `file` and `pos`. Only works on servers with `gtid_mode=ON`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gtids: Option<String>,
+}
+
+fn first_position() -> u64 {
+    4
+}
+
+impl BinlogOffset {
+    /// Returns the offsets for a Debezium MySQL connector with the topic prefix, whose single
+    /// source partition is `{"server": <topic prefix>}`
+    pub fn connector_offsets(&self, topic_prefix: &str) -> ConnectorOffsets {
+        let mut offset = json!({
+            "file": self.file,
+            "pos": self.pos,
+        });
+        if let Some(gtids) = &self.gtids {
+            offset["gtids"] = json!(gtids);
+        }
+
+        ConnectorOffsets {
+            offsets: vec![ConnectorOffset {
+                partition: json!({ "server": topic_prefix }),
+                offset: Some(offset),
+            }],
+        }
+    }
+}
+
+/// Settings for topics created ahead of the connector
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TopicSettings {
+    /// Defaults to 1
+    pub partitions: Option<i32>,
+    /// How long records are kept; the broker's default when unset, and `-1` keeps them forever
+    pub retention_ms: Option<i64>,
+    /// Compact the topic rather than deleting old records
+    pub compact: bool,
+}
+
+/// A compatibility level for the schemas of a subject
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Compatibility {
+    None,
+    Backward,
+    BackwardTransitive,
+    Forward,
+    ForwardTransitive,
+    Full,
+    FullTransitive,
+}
+
+#[test]
+fn test_binlog_connector_offsets() {
+    let offset: BinlogOffset = serde_json::from_str(r#"{"file": "mysql-bin.000003", "gtids": "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5"}"#).unwrap();
+    assert_eq!(offset.pos, 4);
+
+    let offsets = serde_json::to_value(offset.connector_offsets("demo_1")).unwrap();
+    assert_eq!(
+        offsets,
+        json!({
+            "offsets": [{
+                "partition": { "server": "demo_1" },
+                "offset": { "file": "mysql-bin.000003", "pos": 4, "gtids": "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5" }
+            }]
+        })
+    );
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::event::{Event, EventError};
+use crate::types::testbed::Compatibility;
+
+/// A topic in the broker, with the settings that differ from the broker's defaults
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicInfo {
+    pub name: String,
+    pub partitions: usize,
+    pub config: BTreeMap<String, String>,
+}
+
+/// A version of a subject's schema, as returned by `/subjects/<subject>/versions/<version>`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    pub subject: String,
+    pub version: i32,
+    pub id: i32,
+    /// `AVRO` when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<String>,
+    pub schema: String,
+}
+
+/// A subject with its versions and compatibility level
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubjectInfo {
+    pub subject: String,
+    pub versions: Vec<i32>,
+    /// The subject's own level, or the registry's global level when it has none
+    pub compatibility: Compatibility,
+}
+
+/// A `ByLogicalTableRouter` transform of a testbed's connector, which moves the records of the topics
+/// that `regex` matches to `replacement`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicRoute {
+    pub regex: String,
+    pub replacement: String,
+}
+
+impl TopicRoute {
+    /// Returns the topic the route moves the topic to, if it matches. As in Kafka Connect, the regex
+    /// must match the whole topic, and `$1` in the replacement is the regex's first group
+    pub fn route(&self, topic: &str) -> Option<String> {
+        let regex = Regex::new(&format!("^(?:{})$", self.regex)).ok()?;
+        if !regex.is_match(topic) {
+            return None;
+        }
+
+        // `$1_all` is group `1_all` to the regex crate but group 1 to Java
+        let replacement = Regex::new(r"\$(\d+)").unwrap().replace_all(&self.replacement, "$${${1}}");
+        Some(regex.replace(topic, replacement.as_ref()).into_owned())
+    }
+}
+
+/// Returns the topic of one of a testbed's tables: `<testbed>.<testbed>.<table>`, moved by each of the
+/// connector's routes in turn
+pub fn table_topic(testbed_name: &str, table: &str, routes: &[TopicRoute]) -> String {
+    let topic = format!("{testbed_name}.{testbed_name}.{table}");
+    routes.iter().fold(topic, |topic, route| route.route(&topic).unwrap_or(topic))
+}
+
+/// Where in a topic to start reading
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartPosition {
+    Earliest,
+    /// Only events written after the read starts, so usually an empty page
+    Latest,
+    /// The same offset in every partition
+    Offset(i64),
+    /// The offset per partition, as returned in a page's `next`; other partitions start at the earliest
+    Partitions(BTreeMap<i32, i64>),
+    /// The first events at or after the timestamp, in milliseconds since the epoch
+    Timestamp(i64),
+}
+
+impl FromStr for StartPosition {
+    type Err = String;
+
+    /// Parses `earliest`, `latest`, an offset, or `<partition>:<offset>,...`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "earliest" => Ok(StartPosition::Earliest),
+            "latest" => Ok(StartPosition::Latest),
+            _ if s.contains(':') => s
+                .split(',')
+                .map(|p| {
+                    let (partition, offset) = p.split_once(':').ok_or_else(|| format!("not a partition offset: {p}"))?;
+                    let partition = partition.trim().parse().map_err(|_| format!("not a partition: {partition}"))?;
+                    let offset = offset.trim().parse().map_err(|_| format!("not an offset: {offset}"))?;
+                    Ok((partition, offset))
+                })
+                .collect::<Result<_, String>>()
+                .map(StartPosition::Partitions),
+            _ => s
+                .parse()
+                .map(StartPosition::Offset)
+                .map_err(|_| format!("not earliest, latest, an offset or partition offsets: {s}")),
+        }
+    }
+}
+
+/// A page of decoded events from one topic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventPage {
+    pub topic: String,
+    pub events: Vec<TopicEvent>,
+    /// The offset to read the next page from in each partition, to be passed as `from`
+    pub next: String,
+}
+
+/// A record from a testbed topic with its key and value decoded to JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopicEvent {
+    pub partition: i32,
+    pub offset: i64,
+    /// The record's timestamp in milliseconds since the epoch
+    pub timestamp: Option<i64>,
+    pub key: Value,
+    /// `null` for a tombstone
+    pub value: Value,
+    pub headers: Vec<EventHeader>,
+}
+
+impl TopicEvent {
+    /// Deserializes the value as a Debezium event with rows of type `T`
+    pub fn event<T: DeserializeOwned>(&self) -> Result<Event<T>, EventError> {
+        Event::from_json_value(self.value.clone())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventHeader {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+#[test]
+fn test_parse_start_position() {
+    assert_eq!("earliest".parse(), Ok(StartPosition::Earliest));
+    assert_eq!("42".parse(), Ok(StartPosition::Offset(42)));
+    assert_eq!(
+        "0:15,1:20".parse(),
+        Ok(StartPosition::Partitions(BTreeMap::from([(0, 15), (1, 20)])))
+    );
+    assert!("soon".parse::<StartPosition>().is_err());
+    assert!("0:x".parse::<StartPosition>().is_err());
+}
+
+#[test]
+fn test_table_topic() {
+    assert_eq!(table_topic("demo_1", "orders", &[]), "demo_1.demo_1.orders");
+
+    let routes = vec![TopicRoute {
+        regex: r"demo_1\.demo_1\.(orders|shipments)".to_string(),
+        replacement: "demo_1.$1_all".to_string(),
+    }];
+    assert_eq!(table_topic("demo_1", "orders", &routes), "demo_1.orders_all");
+    assert_eq!(table_topic("demo_1", "customers", &routes), "demo_1.demo_1.customers");
+
+    // the regex must match the whole topic
+    let partial = vec![TopicRoute {
+        regex: "orders".to_string(),
+        replacement: "all".to_string(),
+    }];
+    assert_eq!(table_topic("demo_1", "orders", &partial), "demo_1.demo_1.orders");
+}
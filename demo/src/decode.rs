@@ -0,0 +1,75 @@
+use debezium_testbed_client::protobuf;
+use debezium_testbed_client::types::Converter;
+use debezium_testbed_client::Event;
+use schema_registry_converter::async_impl::avro::AvroDecoder;
+use schema_registry_converter::async_impl::proto_decoder::ProtoDecoder;
+use schema_registry_converter::async_impl::schema_registry::SrSettings;
+use serde::de::DeserializeOwned;
+
+use crate::DEMO_SCHEMA_REGISTRY_URL;
+
+/// A decoded key or value
+#[derive(Debug)]
+pub enum Decoded {
+    String(String),
+    Avro(apache_avro::types::Value),
+    /// JSON, or Protobuf converted to JSON with the field names from its schema
+    Json(serde_json::Value),
+}
+
+impl Decoded {
+    /// Deserializes a decoded record value into a typed Debezium event
+    pub fn event<T: DeserializeOwned>(&self) -> Result<Event<T>, Box<dyn std::error::Error + Send + Sync>> {
+        match self {
+            Decoded::String(s) => Ok(Event::from_json(Some(s.as_bytes()))?),
+            Decoded::Avro(value) => Ok(Event::from_avro(Some(value))?),
+            Decoded::Json(value) => Ok(Event::from_json_value(value.clone())?),
+        }
+    }
+}
+
+/// Decodes keys or values written with any of the testbed's converters
+pub struct Decoder<'a> {
+    converter: Converter,
+    avro: AvroDecoder<'a>,
+    proto: ProtoDecoder<'a>,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(converter: Converter) -> Self {
+        let sr_settings = SrSettings::new(DEMO_SCHEMA_REGISTRY_URL.to_string());
+        Decoder {
+            converter,
+            avro: AvroDecoder::new(sr_settings.clone()),
+            proto: ProtoDecoder::new(sr_settings),
+        }
+    }
+
+    pub async fn decode(&self, payload: &[u8]) -> Result<Decoded, Box<dyn std::error::Error + Send + Sync>> {
+        let decoded = match self.converter {
+            Converter::String => Decoded::String(String::from_utf8(payload.to_vec())?),
+            Converter::Avro => Decoded::Avro(self.avro.decode(Some(payload)).await?.value),
+            Converter::Json => Decoded::Json(serde_json::from_slice(payload)?),
+            Converter::JsonSchema => Decoded::Json(serde_json::from_slice(strip_wire_header(payload)?)?),
+            Converter::Protobuf => {
+                let decoded = self
+                    .proto
+                    .decode_with_context(Some(payload))
+                    .await?
+                    .ok_or("payload is not in the Schema Registry wire format")?;
+                Decoded::Json(protobuf::to_json(&decoded.value, &decoded.context.context))
+            }
+        };
+
+        Ok(decoded)
+    }
+}
+
+/// Strips the Schema Registry wire format header, a zero magic byte followed by the 4 byte schema id.
+/// JSON Schema payloads are plain JSON after it, so they don't need the schema to be read
+fn strip_wire_header(payload: &[u8]) -> Result<&[u8], Box<dyn std::error::Error + Send + Sync>> {
+    match payload {
+        [0, _, _, _, _, rest @ ..] => Ok(rest),
+        _ => Err("payload is not in the Schema Registry wire format".into()),
+    }
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use debezium_testbed_client::mysql::prelude::Queryable;
+use debezium_testbed_client::{debezium_test, Op, TestbedHandle};
+use serde::Deserialize;
+
+/// A row of the `example` table
+#[derive(Debug, Deserialize)]
+struct Example {
+    id: u32,
+}
+
+#[debezium_test(template = "mydb", value_converter = "json")]
+async fn test_example_events(testbed: TestbedHandle) {
+    testbed
+        .conn()
+        .query_drop("INSERT INTO example (id) VALUES (42)")
+        .unwrap();
+
+    let events = testbed
+        .events("example")
+        .wait_for(1, Duration::from_secs(30))
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1, "no events on {}", testbed.topic("example"));
+
+    let event = events[0].event::<Example>().unwrap();
+    let change = event.change().expect("a change event");
+    assert_eq!(change.op, Op::Create);
+    assert_eq!(change.after.as_ref().map(|row| row.id), Some(42));
+}
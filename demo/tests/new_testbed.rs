@@ -3,41 +3,34 @@ use std::thread;
 use std::time::Duration;
 
 use demo::consumer::check_for_topic;
-use demo::mysql::{add_test_data, get_mysql_pool};
-use demo::{TestBed, DEMO_SCHEMA_REGISTRY_URL};
+use demo::decode::Decoder;
+use debezium_testbed_client::{Op, TestBedOptions, TestbedClient};
 use rdkafka::message::Message;
-use schema_registry_converter::async_impl::avro::AvroDecoder;
-use schema_registry_converter::async_impl::schema_registry::SrSettings;
+use serde::Deserialize;
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
+/// A row of the `example` table
+#[derive(Debug, Deserialize)]
+struct Example {
+    id: u32,
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn test_testbed() {
     tracing_subscriber::fmt::init();
     let name = "demo";
 
-    let testbed_url = format!("http://localhost:8000/testbed/{}", name);
-    //let shutdown_url = "http://localhost:8000/shutdown/";
-
-    // Get a new testbed by POST to the url with the testbed name
-    let client = reqwest::Client::new();
-    let json = client
-        .post(testbed_url)
-        .send()
-        .await
-        .unwrap()
-        .text()
-        .await
-        .unwrap();
-
-    let testbed: TestBed = serde_json::from_str(&json).unwrap();
-    let testbed_name = testbed.name;
+    // Get a new testbed named after the test, deleted when the test ends
+    let client = TestbedClient::default();
+    let testbed = client.create_guarded(name, &TestBedOptions::default()).await.unwrap();
+    let testbed_name = testbed.name.clone();
+    let value_converter = testbed.value_converter;
 
     info!("Testbed name: {testbed_name:?}");
 
     // Add init test data
-    let pool = get_mysql_pool(&testbed_name);
-    add_test_data(pool.clone(), 0);
+    add_test_data(&client, &testbed_name, 0).await;
 
     let topic_name = format!("{}.{}.example", testbed_name, testbed_name);
 
@@ -49,8 +42,7 @@ async fn test_testbed() {
     let has_messages = testbed_has_messages.clone();
     let token = cancellation_token.clone();
     let poll_handle = tokio::spawn(async move {
-        let sr_settings = SrSettings::new(DEMO_SCHEMA_REGISTRY_URL.to_string());
-        let decoder = AvroDecoder::new(sr_settings.clone());
+        let decoder = Decoder::new(value_converter);
         let topic = topic_name.clone();
 
         let mut topic_created = false;
@@ -115,9 +107,14 @@ async fn test_testbed() {
                         info!("Got message: {message:?}");
     
                         if let Some(payload) = message.payload() {
-                            let value = decoder.decode(Some(payload)).await.unwrap();
-                            let value = value.value;
+                            let value = decoder.decode(payload).await.unwrap();
                             info!("Got value: {value:?}");
+
+                            let event = value.event::<Example>().unwrap();
+                            let change = event.change().expect("a change event");
+                            assert!(matches!(change.op, Op::Create | Op::Read));
+                            let row = change.after.as_ref().expect("the inserted row");
+                            info!("Got row {} at {}:{}", row.id, change.source.file, change.source.pos);
                             *has_messages.lock().unwrap() = true;
                         }
                     }
@@ -135,7 +132,7 @@ async fn test_testbed() {
     while seconds <= total_seconds {
         info!("Sleeping test thread for {seconds:?} of {total_seconds:?} seconds.");
         thread::sleep(Duration::from_secs(1));
-        add_test_data(pool.clone(), seconds);
+        add_test_data(&client, &testbed_name, seconds).await;
         seconds += 1;
         let had_messages = *testbed_has_messages.lock().unwrap();
         if had_messages {
@@ -150,3 +147,9 @@ async fn test_testbed() {
     info!("Had messages in testbed: {had_messages:?}");
     assert!(had_messages);
 }
+
+async fn add_test_data(client: &TestbedClient, testbed_name: &str, num: u32) {
+    info!("adding test data with value: {num:?}");
+    let sql = format!("INSERT INTO example (id) VALUES ({});", num);
+    client.execute(testbed_name, &sql).await.unwrap();
+}
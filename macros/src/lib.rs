@@ -0,0 +1,130 @@
+//! The `#[debezium_test]` attribute, re-exported by `debezium-testbed-client`
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Expr, ExprLit, ItemFn, Lit, MetaNameValue, Token};
+
+/// The settings the attribute takes; `name` and `url` pick the testbed name prefix and the server,
+/// and the rest are `TestBedOptions`
+const SETTINGS: [&str; 11] = [
+    "name",
+    "url",
+    "template",
+    "server",
+    "debezium",
+    "clone_strategy",
+    "fixtures",
+    "phase",
+    "key_converter",
+    "value_converter",
+    "compatibility",
+];
+
+/// Runs an async test against a fresh testbed, which is deleted when the test ends, even if it
+/// panics. The test takes a single `TestbedHandle`:
+///
+/// ```ignore
+/// #[debezium_test(template = "orders", server = "5.7", fixtures = "default")]
+/// async fn test_orders(testbed: TestbedHandle) {
+///     testbed.conn().query_drop("DELETE FROM orders").unwrap();
+///     let events = testbed.events("orders").wait_for(1, Duration::from_secs(30)).await.unwrap();
+/// }
+/// ```
+///
+/// The testbed is named after the test unless `name` is given, and created on the server at
+/// `http://localhost:8000` unless `url` is given. The test may return `()` or a `Result`
+#[proc_macro_attribute]
+pub fn debezium_test(args: TokenStream, item: TokenStream) -> TokenStream {
+    match expand(args.into(), item.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(args: TokenStream2, item: TokenStream2) -> syn::Result<TokenStream2> {
+    let settings = parse_settings(args)?;
+    let test: ItemFn = syn::parse2(item)?;
+
+    if test.sig.asyncness.is_none() {
+        return Err(syn::Error::new(test.sig.fn_token.span(), "a debezium_test must be async"));
+    }
+    if test.sig.inputs.len() != 1 {
+        return Err(syn::Error::new(
+            test.sig.inputs.span(),
+            "a debezium_test takes a single TestbedHandle",
+        ));
+    }
+
+    let attrs = &test.attrs;
+    let vis = &test.vis;
+    let name = &test.sig.ident;
+    let mut inner = test.clone();
+    inner.attrs.clear();
+    inner.vis = syn::Visibility::Inherited;
+
+    let keys = settings.iter().map(|(k, _)| k);
+    let values = settings.iter().map(|(_, v)| v);
+
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() {
+            #inner
+
+            ::debezium_testbed_client::harness::run(
+                stringify!(#name),
+                &[#((#keys, #values)),*],
+                #name,
+            );
+        }
+    })
+}
+
+/// Parses `key = "value", ...`, checking each key is a known setting
+fn parse_settings(args: TokenStream2) -> syn::Result<Vec<(String, String)>> {
+    let args = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(args)?;
+
+    let mut settings = vec![];
+    for arg in args {
+        let key = arg
+            .path
+            .get_ident()
+            .map(|i| i.to_string())
+            .filter(|k| SETTINGS.contains(&k.as_str()))
+            .ok_or_else(|| {
+                syn::Error::new(arg.path.span(), format!("expected one of: {}", SETTINGS.join(", ")))
+            })?;
+        if settings.iter().any(|(k, _)| *k == key) {
+            return Err(syn::Error::new(arg.path.span(), format!("{key} is given twice")));
+        }
+
+        let value = match &arg.value {
+            Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => s.value(),
+            other => return Err(syn::Error::new(other.span(), "expected a string")),
+        };
+        settings.push((key, value));
+    }
+
+    Ok(settings)
+}
+
+#[test]
+fn test_parse_settings() {
+    let settings = parse_settings(quote!(template = "orders", server = "5.7")).unwrap();
+    assert_eq!(
+        settings,
+        vec![
+            ("template".to_string(), "orders".to_string()),
+            ("server".to_string(), "5.7".to_string()),
+        ]
+    );
+
+    assert!(parse_settings(quote!()).unwrap().is_empty());
+    assert!(parse_settings(quote!(templates = "orders")).is_err());
+    assert!(parse_settings(quote!(server = 5.7)).is_err());
+    assert!(parse_settings(quote!(server = "5.7", server = "8.0")).is_err());
+}
@@ -0,0 +1,106 @@
+//! Writes random rows to a testbed's tables through a running testbed server:
+//!
+//! ```sh
+//! cargo run --bin generate -- my_testbed --tables orders,customers --operations 1000 --rate 50 --updates 0.3
+//! ```
+
+use std::process::ExitCode;
+
+use debezium_testbed_client::types::GenerateRequest;
+use debezium_testbed_client::{TestbedClient, TESTBED_URL};
+
+const USAGE: &str = "usage: generate <testbed> [--tables a,b] [--operations n] [--rate per_second] \
+                     [--updates ratio] [--deletes ratio] [--seed n] [--url url]";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    testbed: String,
+    url: String,
+    request: GenerateRequest,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut testbed = None;
+    let mut url = TESTBED_URL.to_string();
+    let mut request = GenerateRequest::default();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            if testbed.replace(arg).is_some() {
+                return Err("only one testbed may be given".to_string());
+            }
+            continue;
+        }
+
+        let value = args.next().ok_or_else(|| format!("{arg} needs a value"))?;
+        let invalid = || format!("invalid value for {arg}: {value}");
+        match arg.as_str() {
+            "--tables" => request.tables = value.split(',').filter(|t| !t.is_empty()).map(str::to_string).collect(),
+            "--operations" => request.operations = value.parse().map_err(|_| invalid())?,
+            "--rate" => request.rate = Some(value.parse().map_err(|_| invalid())?),
+            "--updates" => request.update_ratio = value.parse().map_err(|_| invalid())?,
+            "--deletes" => request.delete_ratio = value.parse().map_err(|_| invalid())?,
+            "--seed" => request.seed = Some(value.parse().map_err(|_| invalid())?),
+            "--url" => url = value,
+            _ => return Err(format!("unknown option: {arg}")),
+        }
+    }
+
+    let testbed = testbed.ok_or("a testbed must be given")?;
+    request.validate()?;
+
+    Ok(Args { testbed, url, request })
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let client = TestbedClient::new(&args.url);
+    match client.generate(&args.testbed, &args.request).await {
+        Ok(summary) => {
+            println!(
+                "{} inserted, {} updated, {} deleted, {} skipped in {}ms",
+                summary.inserted, summary.updated, summary.deleted, summary.skipped, summary.elapsed_ms
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[test]
+fn test_parse_args() {
+    let args = |s: &str| parse_args(s.split_whitespace().map(str::to_string));
+
+    let parsed = args("orders_test --tables orders,customers --operations 500 --rate 20 --updates 0.25 --seed 7").unwrap();
+    assert_eq!(parsed.testbed, "orders_test");
+    assert_eq!(parsed.url, TESTBED_URL);
+    assert_eq!(parsed.request.tables, vec!["orders", "customers"]);
+    assert_eq!(parsed.request.operations, 500);
+    assert_eq!(parsed.request.rate, Some(20.0));
+    assert_eq!(parsed.request.update_ratio, 0.25);
+    assert_eq!(parsed.request.delete_ratio, 0.0);
+    assert_eq!(parsed.request.seed, Some(7));
+
+    let parsed = args("orders_test --url http://testbed:8000").unwrap();
+    assert_eq!(parsed.url, "http://testbed:8000");
+    assert_eq!(parsed.request, GenerateRequest::default());
+
+    assert!(args("--operations 10").is_err());
+    assert!(args("orders_test other_test").is_err());
+    assert!(args("orders_test --operations").is_err());
+    assert!(args("orders_test --operations many").is_err());
+    assert!(args("orders_test --updates 0.8 --deletes 0.5").is_err());
+    assert!(args("orders_test --verbose 1").is_err());
+}
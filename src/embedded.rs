@@ -0,0 +1,186 @@
+//! The stack run from inside a test binary, without the HTTP server. The first test to ask for the
+//! stack starts it, or adopts one that's already running, and every other test in the process
+//! shares it:
+//!
+//! ```ignore
+//! let testbed = debezium_testbed::embedded::new_testbed("my_test", &TestBedOptions::default()).await?;
+//! ```
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use mysql::prelude::Queryable;
+use tokio::sync::OnceCell;
+use tracing::{error, info};
+
+use crate::stack::containers::kafka_connect::DEBEZIUM_VERSIONS;
+use crate::stack::containers::mysql::{MySqlServer, MYSQL_SERVERS};
+use crate::stack::kafka_connect::KafkaConnectClient;
+use crate::stack::mysql::get_mysql_pool;
+use crate::stack::runtime::{ContainerRuntime, DockerRuntime};
+use crate::stack::{stack_containers, start_stack, stop_stack, teardown_stack, Stack, TestBed, TestBedOptions};
+
+/// The connector every Connect worker must have installed before testbeds can be created
+const MYSQL_CONNECTOR_CLASS: &str = "io.debezium.connector.mysql.MySqlConnector";
+
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How the embedded stack is started
+#[derive(Debug, Clone)]
+pub struct EmbeddedOptions {
+    /// Stop and remove the stack when the process exits, if the process started it. An adopted
+    /// stack is always left running
+    pub stop_at_exit: bool,
+    /// How long to wait for Kafka Connect and MySQL to be ready
+    pub startup_timeout: Duration,
+}
+
+impl Default for EmbeddedOptions {
+    fn default() -> Self {
+        EmbeddedOptions {
+            stop_at_exit: false,
+            startup_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// How the process got its stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackOrigin {
+    /// The process started the stack
+    Started,
+    /// Every container was already running, i.e. under `cargo run` or an earlier test binary
+    Adopted,
+}
+
+static STACK: OnceCell<StackOrigin> = OnceCell::const_new();
+
+/// Starts the stack, or adopts it if every container is already running, and waits until it's
+/// ready. Only the first call in the process does anything; the options of later calls are ignored
+pub async fn start(options: &EmbeddedOptions) -> Result<StackOrigin> {
+    let origin = STACK.get_or_try_init(|| start_once(options)).await?;
+    Ok(*origin)
+}
+
+/// Creates a testbed, starting the stack with the default options if it hasn't been
+pub async fn new_testbed(test_name: &str, options: &TestBedOptions) -> Result<TestBed> {
+    start(&EmbeddedOptions::default()).await?;
+    Stack::new_testbed(test_name, options).await
+}
+
+pub async fn delete_testbed(testbed_name: &str) -> Result<()> {
+    Stack::delete_testbed(testbed_name).await
+}
+
+async fn start_once(options: &EmbeddedOptions) -> Result<StackOrigin> {
+    let runtime = DockerRuntime::connect()?;
+
+    let origin = if all_running(&runtime).await {
+        info!("Adopting the running stack");
+        StackOrigin::Adopted
+    } else {
+        info!("Starting the stack");
+        stop_stack(&runtime).await;
+        teardown_stack(&runtime).await;
+        start_stack(&runtime).await?;
+
+        if options.stop_at_exit {
+            // SAFETY: the handler is a plain function with no arguments, as atexit requires
+            if unsafe { libc::atexit(stop_stack_at_exit) } != 0 {
+                error!("Could not register the stack to stop at exit");
+            }
+        }
+        StackOrigin::Started
+    };
+
+    wait_until_ready(options.startup_timeout).await?;
+    info!("Stack is ready");
+
+    Ok(origin)
+}
+
+/// True when every container in the stack is running
+async fn all_running(runtime: &dyn ContainerRuntime) -> bool {
+    for name in stack_containers() {
+        let running = runtime.inspect(name).await.is_ok_and(|s| s.running);
+        if !running {
+            info!("{name} isn't running");
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Waits until every Connect worker has the MySQL connector installed and every MySQL server
+/// takes connections
+async fn wait_until_ready(timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    for debezium in &DEBEZIUM_VERSIONS {
+        let kafka_connect = KafkaConnectClient::for_debezium(debezium);
+        wait_for(deadline, debezium.worker, || async {
+            match kafka_connect.connector_plugins().await {
+                Ok(plugins) => plugins.iter().any(|p| p.class == MYSQL_CONNECTOR_CLASS),
+                Err(_) => false,
+            }
+        })
+        .await?;
+    }
+
+    for server in MYSQL_SERVERS {
+        wait_for(deadline, server.name, || async move {
+            tokio::task::spawn_blocking(move || is_mysql_ready(&server))
+                .await
+                .unwrap_or(false)
+        })
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn wait_for<F, Fut>(deadline: Instant, name: &str, mut is_ready: F) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    loop {
+        if is_ready().await {
+            info!("{name} is ready");
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(anyhow!("{name} wasn't ready in time"));
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+    }
+}
+
+fn is_mysql_ready(server: &MySqlServer) -> bool {
+    let Ok(pool) = get_mysql_pool(server) else {
+        return false;
+    };
+    pool.get_conn()
+        .and_then(|mut conn| conn.query_drop("SELECT 1"))
+        .is_ok()
+}
+
+/// Stops and removes the stack at process exit, on a runtime of its own as the process's
+/// runtimes may be gone
+extern "C" fn stop_stack_at_exit() {
+    match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime.block_on(async {
+            // panicking here would abort the process
+            info!("Stopping the stack at exit");
+            if let Err(e) = Stack::stop().await {
+                error!("Could not stop the stack at exit: {e:?}");
+            }
+            if let Err(e) = Stack::teardown().await {
+                error!("Could not tear down the stack at exit: {e:?}");
+            }
+        }),
+        Err(e) => eprintln!("Could not stop the stack at exit: {e}"),
+    }
+}
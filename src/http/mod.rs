@@ -1,20 +1,253 @@
-use rocket::{get, post, Shutdown};
-use tracing::info;
-use crate::stack::{Stack, TestBed};
-use rocket::serde::json::Json;
+use rocket::{delete, get, patch, post, FromForm, Request, Shutdown};
+use rocket::http::Status;
+use rocket::response::{self, Responder, Response};
+use tracing::{error, info};
+use crate::stack::{Stack, TestBed, TestBedOptions};
+use crate::stack::error::TestBedError;
+use crate::stack::kafka_connect::{ConnectorOffsets, ConnectorStatus};
+use crate::stack::offsets::BinlogOffset;
+use crate::stack::kafka_admin::TopicInfo;
+use crate::stack::events::{EventPage, StartPosition, DEFAULT_EVENT_LIMIT, MAX_EVENT_LIMIT};
+use crate::stack::schema_registry::{Schema, SubjectInfo};
+use crate::stack::sql::{SqlRequest, SqlResult};
+use crate::stack::generator::{GenerateRequest, GenerateSummary};
+use crate::stack::signals::{SnapshotProgress, SnapshotRequest, SnapshotSignal};
+use rocket::serde::json::{self, Json};
 
 
-#[post("/testbed/<name>")]
-pub async fn testbed(name: &str) -> Json<TestBed> {
-    let testbed = Stack::new_testbed(name).await;
-    Json(testbed)
+pub use debezium_testbed_client::types::ErrorBody;
+
+/// An error from a route; errors in the request are reported as `400`, connector configs
+/// Kafka Connect rejects as `422`, and everything else as `500`
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: Status,
+    pub body: ErrorBody,
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        let mut config_errors = vec![];
+        let status = match e.downcast_ref::<TestBedError>() {
+            Some(TestBedError::UnknownServer(_))
+            | Some(TestBedError::UnknownDebeziumVersion(_))
+            | Some(TestBedError::UnsupportedServer { .. })
+            | Some(TestBedError::UnknownTemplate(_))
+            | Some(TestBedError::UnknownFixtures(_))
+            | Some(TestBedError::UnknownTable(_))
+            | Some(TestBedError::InvalidSql(_)) => Status::BadRequest,
+            Some(TestBedError::UnknownTestBed(_)) | Some(TestBedError::UnknownSubject(_)) => Status::NotFound,
+            Some(TestBedError::InvalidConnectorConfig(errors)) => {
+                config_errors = errors.clone();
+                Status::UnprocessableEntity
+            }
+            None => {
+                error!("{e:?}");
+                Status::InternalServerError
+            }
+        };
+
+        ApiError {
+            status,
+            body: ErrorBody {
+                error: e.to_string(),
+                config_errors,
+            },
+        }
+    }
+}
+
+impl ApiError {
+    pub fn bad_request(error: impl ToString) -> Self {
+        ApiError {
+            status: Status::BadRequest,
+            body: ErrorBody {
+                error: error.to_string(),
+                config_errors: vec![],
+            },
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ApiError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        Response::build_from(Json(self.body).respond_to(request)?)
+            .status(self.status)
+            .ok()
+    }
+}
+
+/// Returns the JSON body of a request, or the defaults if the body is empty
+fn body_or_default<T: Default>(body: Result<Json<T>, json::Error<'_>>) -> Result<T, ApiError> {
+    match body {
+        Ok(body) => Ok(body.into_inner()),
+        Err(json::Error::Parse(body, _)) if body.trim().is_empty() => Ok(T::default()),
+        Err(e) => Err(ApiError::bad_request(e)),
+    }
+}
+
+/// Creates a testbed; the body is an optional JSON `TestBedOptions`
+#[post("/testbed/<name>", data = "<options>")]
+pub async fn testbed(name: &str, options: Result<Json<TestBedOptions>, json::Error<'_>>) -> Result<Json<TestBed>, ApiError> {
+    let options = body_or_default(options)?;
+    let testbed = Stack::new_testbed(name, &options).await?;
+    Ok(Json(testbed))
+}
+
+/// Returns the live testbed
+#[get("/testbed/<name>")]
+pub async fn get_testbed(name: &str) -> Result<Json<TestBed>, ApiError> {
+    Ok(Json(Stack::testbed(name)?))
+}
+
+/// Returns every live testbed, sorted by name
+#[get("/testbeds")]
+pub async fn list_testbeds() -> Json<Vec<TestBed>> {
+    Json(Stack::testbeds())
+}
+
+/// Deletes the testbed's connector and database
+#[delete("/testbed/<name>")]
+pub async fn delete_testbed(name: &str) -> Result<(), ApiError> {
+    Stack::delete_testbed(name).await?;
+    Ok(())
+}
+
+/// Runs one or more statements against the testbed database
+#[post("/testbed/<name>/sql", data = "<request>")]
+pub async fn execute_sql(name: &str, request: Json<SqlRequest>) -> Result<Json<SqlResult>, ApiError> {
+    if request.sql.trim().is_empty() {
+        return Err(ApiError::bad_request("no sql to run"));
+    }
+    Ok(Json(Stack::execute_sql(name, &request.sql).await?))
+}
+
+/// Writes random rows to the testbed's tables; the body is an optional JSON `GenerateRequest`
+#[post("/testbed/<name>/generate", data = "<request>")]
+pub async fn generate(name: &str, request: Result<Json<GenerateRequest>, json::Error<'_>>) -> Result<Json<GenerateSummary>, ApiError> {
+    let request = body_or_default(request)?;
+    request.validate().map_err(ApiError::bad_request)?;
+    Ok(Json(Stack::generate(name, &request).await?))
+}
+
+/// Returns the state of the testbed's connector and its tasks
+#[get("/testbed/<name>/connector")]
+pub async fn connector_status(name: &str) -> Result<Json<ConnectorStatus>, ApiError> {
+    Ok(Json(Stack::connector_status(name).await?))
+}
+
+#[post("/testbed/<name>/connector/pause")]
+pub async fn pause_connector(name: &str) -> Result<Json<ConnectorStatus>, ApiError> {
+    Ok(Json(Stack::pause_connector(name).await?))
+}
+
+#[post("/testbed/<name>/connector/resume")]
+pub async fn resume_connector(name: &str) -> Result<Json<ConnectorStatus>, ApiError> {
+    Ok(Json(Stack::resume_connector(name).await?))
+}
+
+/// The query of a connector restart, named as in the Kafka Connect REST API
+#[derive(Debug, FromForm)]
+pub struct RestartOptions {
+    #[field(name = "includeTasks")]
+    pub include_tasks: bool,
+    #[field(name = "onlyFailed")]
+    pub only_failed: bool,
+}
+
+#[post("/testbed/<name>/connector/restart?<options..>")]
+pub async fn restart_connector(name: &str, options: RestartOptions) -> Result<Json<ConnectorStatus>, ApiError> {
+    let status = Stack::restart_connector(name, options.include_tasks, options.only_failed).await?;
+    Ok(Json(status))
+}
+
+/// Returns the binlog position of the testbed's connector
+#[get("/testbed/<name>/offsets")]
+pub async fn connector_offsets(name: &str) -> Result<Json<ConnectorOffsets>, ApiError> {
+    Ok(Json(Stack::connector_offsets(name).await?))
+}
+
+/// Moves the testbed's connector to another binlog position
+#[patch("/testbed/<name>/offsets", data = "<offset>")]
+pub async fn seek_connector(name: &str, offset: Json<BinlogOffset>) -> Result<Json<ConnectorOffsets>, ApiError> {
+    Ok(Json(Stack::reset_connector_offsets(name, Some(&offset)).await?))
+}
+
+/// Clears the offsets of the testbed's connector, so it snapshots the database again
+#[delete("/testbed/<name>/offsets")]
+pub async fn reset_connector_offsets(name: &str) -> Result<Json<ConnectorOffsets>, ApiError> {
+    Ok(Json(Stack::reset_connector_offsets(name, None).await?))
+}
+
+/// Returns the testbed's topics, with their partitions and settings
+#[get("/testbed/<name>/topics")]
+pub async fn testbed_topics(name: &str) -> Result<Json<Vec<TopicInfo>>, ApiError> {
+    Ok(Json(Stack::testbed_topics(name).await?))
+}
+
+/// Returns the testbed's registry subjects, with their versions and compatibility levels
+#[get("/testbed/<name>/subjects")]
+pub async fn testbed_subjects(name: &str) -> Result<Json<Vec<SubjectInfo>>, ApiError> {
+    Ok(Json(Stack::testbed_subjects(name).await?))
+}
+
+/// Returns a version of a subject's schema; `version` is a version number or `latest`
+#[get("/testbed/<name>/subjects/<subject>/versions/<version>")]
+pub async fn testbed_schema(name: &str, subject: &str, version: &str) -> Result<Json<Schema>, ApiError> {
+    Ok(Json(Stack::testbed_schema(name, subject, version).await?))
+}
+
+/// The query of an event read
+#[derive(Debug, FromForm)]
+pub struct EventQuery {
+    /// The table whose topic to read
+    pub table: Option<String>,
+    /// `earliest` (the default), `latest`, an offset, or the `next` of the previous page
+    pub from: Option<String>,
+    /// Start at the first events at or after this many milliseconds since the epoch instead
+    pub timestamp: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// Returns a page of decoded events from the topic of one of the testbed's tables
+#[get("/testbed/<name>/events?<query..>")]
+pub async fn testbed_events(name: &str, query: EventQuery) -> Result<Json<EventPage>, ApiError> {
+    let table = query.table.ok_or_else(|| ApiError::bad_request("table is required"))?;
+    let start = match (query.from, query.timestamp) {
+        (Some(_), Some(_)) => return Err(ApiError::bad_request("from and timestamp can't both be given")),
+        (_, Some(timestamp)) => StartPosition::Timestamp(timestamp),
+        (Some(from), None) => from.parse().map_err(ApiError::bad_request)?,
+        (None, None) => StartPosition::Earliest,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_EVENT_LIMIT).min(MAX_EVENT_LIMIT);
+
+    Ok(Json(Stack::testbed_events(name, &table, start, limit).await?))
+}
+
+/// Starts an incremental snapshot of the tables in the testbed
+#[post("/testbed/<name>/snapshot", data = "<request>")]
+pub async fn incremental_snapshot(name: &str, request: Json<SnapshotRequest>) -> Result<Json<SnapshotSignal>, ApiError> {
+    if request.tables.is_empty() {
+        return Err(ApiError::bad_request("no tables to snapshot"));
+    }
+    Ok(Json(Stack::incremental_snapshot(name, &request.tables).await?))
+}
+
+/// Returns the progress of the testbed's incremental snapshot
+#[get("/testbed/<name>/snapshot")]
+pub async fn snapshot_progress(name: &str) -> Result<Json<SnapshotProgress>, ApiError> {
+    Ok(Json(Stack::snapshot_progress(name).await?))
 }
 
 #[get("/shutdown")]
 pub async fn shutdown(shutdown: Shutdown) -> &'static str {
     info!("Shutting down");
-    Stack::stop().await;
-    Stack::teardown().await;
+    if let Err(e) = Stack::stop().await {
+        error!("Could not stop the stack: {e:?}");
+    }
+    if let Err(e) = Stack::teardown().await {
+        error!("Could not tear down the stack: {e:?}");
+    }
     shutdown.notify();
     "Shutting down..."
-}
\ No newline at end of file
+}
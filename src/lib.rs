@@ -1,2 +1,6 @@
 pub mod stack;
-pub mod http;
\ No newline at end of file
+pub mod http;
+pub mod embedded;
+
+pub use stack::kafka_connect::{KafkaConnectClient, KafkaConnectError};
+pub use stack::schema_registry::{SchemaRegistryClient, SchemaRegistryError};
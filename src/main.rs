@@ -1,28 +1,52 @@
 use rocket::fairing::{Fairing, Info, Kind};
-use rocket::http::{Header, Status};
-use rocket::{launch, routes, Request, Response};
+use rocket::http::{Header, Method, Status};
+use rocket::{routes, Request, Response};
 use tracing::info;
 use crate::stack::Stack;
-use crate::http::{testbed, shutdown};
+use crate::http::{testbed, get_testbed, list_testbeds, delete_testbed, execute_sql, generate, shutdown, connector_status, pause_connector, resume_connector, restart_connector, connector_offsets, seek_connector, reset_connector_offsets, incremental_snapshot, snapshot_progress, testbed_topics, testbed_subjects, testbed_schema, testbed_events};
 
 pub mod stack;
 pub mod http;
 
-#[launch]
-async fn rocket() -> _  {
+#[rocket::main]
+async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init(); 
 
     info!("--- STARTUP DOCKER ---");
-    Stack::stop().await;
-    Stack::teardown().await;
-    Stack::start().await;
+    Stack::stop().await?;
+    Stack::teardown().await?;
+    Stack::start().await?;
 
 
     info!("--- STARTUP HTTP ---");
-    rocket::build()
+    let _rocket = rocket::build()
         .attach(CORS)
-        .mount("/", routes![testbed, shutdown])
-        
+        .mount("/", routes![
+            testbed,
+            get_testbed,
+            list_testbeds,
+            delete_testbed,
+            execute_sql,
+            generate,
+            connector_status,
+            pause_connector,
+            resume_connector,
+            restart_connector,
+            connector_offsets,
+            seek_connector,
+            reset_connector_offsets,
+            incremental_snapshot,
+            snapshot_progress,
+            testbed_topics,
+            testbed_subjects,
+            testbed_schema,
+            testbed_events,
+            shutdown
+        ])
+        .launch()
+        .await?;
+
+    Ok(())
 }
 
 
@@ -37,7 +61,7 @@ impl Fairing for CORS {
         }
     }
 
-    async fn on_response<'r>(&self, _request: &'r Request<'_>, response: &mut Response<'r>) {
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
         response.set_header(Header::new("Access-Control-Allow-Origin", "*"));
         response.set_header(Header::new(
             "Access-Control-Allow-Methods",
@@ -45,6 +69,10 @@ impl Fairing for CORS {
         ));
         response.set_header(Header::new("Access-Control-Allow-Headers", "*"));
         response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
-        response.set_status(Status::Ok)
+
+        // preflight requests have no route; everything else keeps its status so errors reach the caller
+        if request.method() == Method::Options {
+            response.set_status(Status::Ok)
+        }
     }
 }
\ No newline at end of file
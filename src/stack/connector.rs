@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::{fs, path::Path};
+
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::stack::containers::kafka_connect::DebeziumVersion;
+use crate::stack::containers::mysql::MySqlServer;
+
+/// Prefixes of the schema history properties before and since Debezium 2.0
+const LEGACY_HISTORY_PREFIX: &str = "database.history.";
+const HISTORY_PREFIX: &str = "schema.history.internal.";
+
+/// A Kafka Connect connector as POSTed to `/connectors`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Connector {
+    pub name: String,
+    pub config: ConnectorConfig,
+    /// `STOPPED` or `PAUSED` to create the connector without starting it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_state: Option<String>,
+}
+
+/// The config of a Debezium MySQL connector. The properties a testbed changes are typed
+/// fields; every other property in the template is kept as is in `other`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectorConfig {
+    #[serde(rename = "connector.class")]
+    pub connector_class: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "database.hostname")]
+    pub database_hostname: String,
+    #[serde(rename = "database.server.id", with = "string_u32")]
+    pub database_server_id: u32,
+    #[serde(rename = "database.include.list", default, skip_serializing_if = "Option::is_none")]
+    pub database_include_list: Option<String>,
+    #[serde(rename = "table.include.list", default, skip_serializing_if = "Option::is_none")]
+    pub table_include_list: Option<String>,
+    #[serde(rename = "table.exclude.list", default, skip_serializing_if = "Option::is_none")]
+    pub table_exclude_list: Option<String>,
+    /// The prefix of every topic for Debezium 2.x
+    #[serde(rename = "topic.prefix", default, skip_serializing_if = "Option::is_none")]
+    pub topic_prefix: Option<String>,
+    /// The prefix of every topic for Debezium 1.x
+    #[serde(rename = "database.server.name", default, skip_serializing_if = "Option::is_none")]
+    pub database_server_name: Option<String>,
+    /// The schema history topic for Debezium 2.x
+    #[serde(rename = "schema.history.internal.kafka.topic", default, skip_serializing_if = "Option::is_none")]
+    pub schema_history_topic: Option<String>,
+    /// The schema history topic for Debezium 1.x
+    #[serde(rename = "database.history.kafka.topic", default, skip_serializing_if = "Option::is_none")]
+    pub database_history_topic: Option<String>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl Connector {
+    /// Reads a connector template, i.e. a template's `connector.json`
+    pub fn from_file(path: &Path) -> Result<Connector> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Returns this template rendered for a testbed database: the connector is named after the
+    /// testbed, captures only the testbed database on the server, and writes to topics prefixed
+    /// with the testbed name. Templates may use the property names of either Debezium major
+    /// version; the rendered config only has those of the release it runs on
+    pub fn render(
+        &self,
+        template_db: &str,
+        testbed_name: &str,
+        server: &MySqlServer,
+        debezium: &DebeziumVersion,
+        server_id: u32,
+    ) -> Connector {
+        let mut config = self.config.clone();
+
+        config.name = Some(testbed_name.to_string());
+        config.database_hostname = server.name.to_string();
+        config.database_server_id = server_id;
+        config.database_include_list = Some(testbed_name.to_string());
+        config.table_include_list = rename_db_in_list(&config.table_include_list, template_db, testbed_name);
+        config.table_exclude_list = rename_db_in_list(&config.table_exclude_list, template_db, testbed_name);
+
+        if debezium.is_legacy() {
+            config.topic_prefix = None;
+            config.schema_history_topic = None;
+            config.database_server_name = Some(testbed_name.to_string());
+            config.database_history_topic = Some(format!("{}.history", testbed_name));
+            config.rename_prefix(HISTORY_PREFIX, LEGACY_HISTORY_PREFIX);
+        } else {
+            config.database_server_name = None;
+            config.database_history_topic = None;
+            config.topic_prefix = Some(testbed_name.to_string());
+            config.schema_history_topic = Some(format!("schema-changes.{}.history", testbed_name));
+            config.rename_prefix(LEGACY_HISTORY_PREFIX, HISTORY_PREFIX);
+        }
+
+        Connector {
+            name: testbed_name.to_string(),
+            config,
+            initial_state: None,
+        }
+    }
+}
+
+impl ConnectorConfig {
+    /// Sets a property that has no typed field, i.e. a converter or transform
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        self.other
+            .insert(name.to_string(), serde_json::Value::String(value.into()));
+    }
+
+    /// Returns a property that has no typed field, if it's set
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.other.get(name).and_then(|v| v.as_str())
+    }
+
+    /// True when the connector captures the table, given as `<db>.<table>`. Like Debezium, the
+    /// include and exclude lists are comma separated regular expressions matching the whole name
+    pub fn captures(&self, table: &str) -> Result<bool> {
+        let matches_any = |list: &str| -> Result<bool> {
+            for pattern in list.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                if Regex::new(&format!("^(?:{pattern})$"))?.is_match(table) {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        };
+
+        match (&self.table_include_list, &self.table_exclude_list) {
+            (Some(include), _) => matches_any(include),
+            (None, Some(exclude)) => Ok(!matches_any(exclude)?),
+            (None, None) => Ok(true),
+        }
+    }
+
+    /// Moves the untyped properties starting with `from` to start with `to`. Where both names are
+    /// set, the one already starting with `to` wins
+    fn rename_prefix(&mut self, from: &str, to: &str) {
+        let names: Vec<String> = self.other.keys().filter(|k| k.starts_with(from)).cloned().collect();
+
+        for name in names {
+            let value = self.other.remove(&name).unwrap();
+            let renamed = format!("{}{}", to, &name[from.len()..]);
+            self.other.entry(renamed).or_insert(value);
+        }
+    }
+}
+
+/// Moves the `<db>.<table>` entries of a comma separated list from the template database to the testbed
+fn rename_db_in_list(list: &Option<String>, template_db: &str, testbed_name: &str) -> Option<String> {
+    let prefix = format!("{}.", template_db);
+
+    list.as_ref().map(|list| {
+        list.split(',')
+            .map(|entry| {
+                let entry = entry.trim();
+                match entry.strip_prefix(&prefix) {
+                    Some(table) => format!("{}.{}", testbed_name, table),
+                    None => entry.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    })
+}
+
+/// Kafka Connect config values are strings, so numbers are read from either and written as strings
+mod string_u32 {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(s) => s.parse().map_err(de::Error::custom),
+            serde_json::Value::Number(n) => n
+                .as_u64()
+                .and_then(|n| u32::try_from(n).ok())
+                .ok_or_else(|| de::Error::custom("not a u32")),
+            other => Err(de::Error::custom(format!("not a u32: {other}"))),
+        }
+    }
+}
+
+#[test]
+fn test_render_connector() {
+    use crate::stack::containers::kafka_connect::DEBEZIUM_24;
+    use crate::stack::containers::mysql::MYSQL_57;
+
+    let json = r#"{"name":"mydb","config":{"connector.class":"io.debezium.connector.mysql.MySqlConnector",
+        "database.hostname":"db","database.server.id":1,"database.include.list":"mydb",
+        "table.include.list":"mydb.example,other.example","database.password":"mydb-secret",
+        "snapshot.mode":"initial","topic.prefix":"dbz","database.server.name":"db",
+        "database.history.kafka.bootstrap.servers":"kafka:9092"}}"#;
+    let template: Connector = serde_json::from_str(json).unwrap();
+
+    let connector = template.render("mydb", "tester_1", &MYSQL_57, &DEBEZIUM_24, 42);
+    let rendered = serde_json::to_value(&connector).unwrap();
+    let config = &rendered["config"];
+
+    assert_eq!(rendered["name"], "tester_1");
+    assert_eq!(config["name"], "tester_1");
+    assert_eq!(config["database.hostname"], "db57");
+    assert_eq!(config["database.server.id"], "42");
+    assert_eq!(config["database.include.list"], "tester_1");
+    assert_eq!(config["table.include.list"], "tester_1.example,other.example");
+    assert_eq!(config["topic.prefix"], "tester_1");
+    assert_eq!(config["schema.history.internal.kafka.topic"], "schema-changes.tester_1.history");
+    assert_eq!(config["schema.history.internal.kafka.bootstrap.servers"], "kafka:9092");
+    assert!(config.get("database.server.name").is_none());
+    assert!(config.get("database.history.kafka.bootstrap.servers").is_none());
+
+    // values that merely contain the template name and unknown properties are left alone
+    assert_eq!(config["database.password"], "mydb-secret");
+    assert_eq!(config["snapshot.mode"], "initial");
+}
+
+#[test]
+fn test_render_legacy_connector() {
+    use crate::stack::containers::kafka_connect::DEBEZIUM_17;
+    use crate::stack::containers::mysql::MYSQL_80;
+
+    let template = Connector::from_file(&crate::stack::templates::get_template("mydb").unwrap().connector_json()).unwrap();
+
+    let connector = template.render("mydb", "tester_1", &MYSQL_80, &DEBEZIUM_17, 42);
+    let rendered = serde_json::to_value(&connector).unwrap();
+    let config = &rendered["config"];
+
+    assert_eq!(config["database.server.name"], "tester_1");
+    assert_eq!(config["database.history.kafka.topic"], "tester_1.history");
+    assert_eq!(config["database.history.kafka.bootstrap.servers"], "kafka:9092");
+    assert!(config.get("topic.prefix").is_none());
+    assert!(config.get("schema.history.internal.kafka.topic").is_none());
+    assert!(config.get("schema.history.internal.kafka.bootstrap.servers").is_none());
+}
+
+#[test]
+fn test_captures() {
+    let mut config: ConnectorConfig = serde_json::from_value(serde_json::json!({
+        "connector.class": "io.debezium.connector.mysql.MySqlConnector",
+        "database.hostname": "db",
+        "database.server.id": "1",
+        "table.include.list": "demo_1.customers,demo_1.purchase_.*"
+    }))
+    .unwrap();
+
+    assert!(config.captures("demo_1.customers").unwrap());
+    assert!(config.captures("demo_1.purchase_orders").unwrap());
+    assert!(!config.captures("demo_1.customers_archive").unwrap());
+
+    config.table_include_list = None;
+    config.table_exclude_list = Some("demo_1.audit_.*".to_string());
+    assert!(config.captures("demo_1.customers").unwrap());
+    assert!(!config.captures("demo_1.audit_log").unwrap());
+}
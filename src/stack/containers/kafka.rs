@@ -1,32 +1,15 @@
-use bollard::models::ContainerCreateResponse;
-use bollard::Docker;
-use bollard::image::CreateImageOptions;
 use anyhow::Result;
 use bollard::models::HostConfig;
 use crate::stack::containers::NETWORK;
 use bollard::container::Config;
-use futures_util::TryStreamExt;
 use tracing::info;
-use bollard::container::CreateContainerOptions;
+use crate::stack::runtime::ContainerRuntime;
 use bollard::models::{PortBinding, PortMap};
 
 pub const KAFKA_IMAGE: &str = "confluentinc/cp-kafka:latest";
 pub const KAFKA: &str = "kafka";
 
-pub async fn get_kafka(docker: &Docker) -> Result<ContainerCreateResponse> {
-    info!("Getting kafka image");
-    let _ = &docker
-        .create_image(
-            Some(CreateImageOptions {
-                from_image: KAFKA_IMAGE,
-                ..Default::default()
-            }),
-            None,
-            None,
-        )
-        .try_collect::<Vec<_>>()
-        .await?;
-
+pub async fn get_kafka(runtime: &dyn ContainerRuntime) -> Result<String> {
     //let mut exposed_ports = HashMap::new();
     //exposed_ports.insert("9092/tcp", HashMap::new());
 
@@ -44,16 +27,16 @@ pub async fn get_kafka(docker: &Docker) -> Result<ContainerCreateResponse> {
     portmap.insert("9092/tcp".to_string(), vec![first_binding, second_binding].into());
 
     let kafka = Config {
-        image: Some(KAFKA_IMAGE),
-        cmd: Some(vec!["/etc/confluent/docker/run"]),
+        image: Some(KAFKA_IMAGE.to_string()),
+        cmd: Some(vec!["/etc/confluent/docker/run".to_string()]),
         env: Some(vec![
-            "KAFKA_ZOOKEEPER_CONNECT=zookeeper:2181",
-            "KAFKA_LISTENERS=INTERNAL://0.0.0.0:9092,OUTSIDE://0.0.0.0:29092",
-            "KAFKA_ADVERTISED_LISTENERS=INTERNAL://kafka:9092,OUTSIDE://localhost:29092",
-            "KAFKA_BROKER_ID=1",
-            "KAFKA_LISTENER_SECURITY_PROTOCOL_MAP=INTERNAL:PLAINTEXT,OUTSIDE:PLAINTEXT",
-            "KAFKA_OFFSETS_TOPIC_REPLICATION_FACTOR=1",
-            "KAFKA_INTER_BROKER_LISTENER_NAME=INTERNAL",
+            "KAFKA_ZOOKEEPER_CONNECT=zookeeper:2181".to_string(),
+            "KAFKA_LISTENERS=INTERNAL://0.0.0.0:9092,OUTSIDE://0.0.0.0:29092".to_string(),
+            "KAFKA_ADVERTISED_LISTENERS=INTERNAL://kafka:9092,OUTSIDE://localhost:29092".to_string(),
+            "KAFKA_BROKER_ID=1".to_string(),
+            "KAFKA_LISTENER_SECURITY_PROTOCOL_MAP=INTERNAL:PLAINTEXT,OUTSIDE:PLAINTEXT".to_string(),
+            "KAFKA_OFFSETS_TOPIC_REPLICATION_FACTOR=1".to_string(),
+            "KAFKA_INTER_BROKER_LISTENER_NAME=INTERNAL".to_string(),
         ]),
         host_config: Some(HostConfig {
             network_mode: Some(String::from(NETWORK)),
@@ -65,16 +48,6 @@ pub async fn get_kafka(docker: &Docker) -> Result<ContainerCreateResponse> {
     };
 
     info!("Creating kafka container");
-    let container = &docker
-        .create_container(
-            Some(CreateContainerOptions {
-                name: "kafka",
-                platform: None,
-            }),
-            kafka,
-        )
-        .await?;
-
-    Ok(container.clone())
+    runtime.create(KAFKA, kafka).await
 }
 
@@ -1,58 +1,158 @@
-use bollard::models::ContainerCreateResponse;
-use bollard::Docker;
-use bollard::image::CreateImageOptions;
 use anyhow::Result;
 use bollard::models::HostConfig;
 use crate::stack::containers::NETWORK;
 use bollard::container::Config;
-use futures_util::TryStreamExt;
 use tracing::info;
-use bollard::container::CreateContainerOptions;
+use crate::stack::runtime::ContainerRuntime;
 use bollard::models::{PortBinding, PortMap};
+use crate::stack::containers::mysql::MySqlServer;
 
 pub const KAFKA_CONNECT: &str = "kafka-connect";
 pub const KAFKA_CONNECT_IMAGE: &str = "confluentinc/cp-kafka-connect-base:latest";
 
-pub async fn get_kafka_connect(docker: &Docker) -> Result<ContainerCreateResponse> {
-    info!("Getting kafka-connect image");
-    let _ = &docker
-        .create_image(
-            Some(CreateImageOptions {
-                from_image: KAFKA_CONNECT_IMAGE,
-                ..Default::default()
-            }),
-            None,
-            None,
-        )
-        .try_collect::<Vec<_>>()
-        .await?;
+/// Where the worker's plugins are installed, and read from on startup
+const PLUGIN_DIR: &str = "/usr/share/confluent-hub-components";
 
+/// Where a Debezium release's plugin is downloaded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginSource {
+    /// `confluent-hub install`, which only carries releases up to 2.4
+    ConfluentHub,
+    /// The plugin archive Debezium publishes to Maven Central
+    Maven,
+}
+
+/// A Debezium release in the stack. Each release is installed in its own Kafka Connect worker,
+/// so the plugins never share a classpath, and each worker is published on its own host port
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebeziumVersion {
+    /// The version a testbed request uses to pick this release, i.e. `1.7`
+    pub version: &'static str,
+    /// The `confluent-hub` version of `debezium/debezium-connector-mysql`
+    pub plugin_version: &'static str,
+    /// The worker's container name and hostname on the `testbed` network
+    pub worker: &'static str,
+    pub host_port: u16,
+    pub source: PluginSource,
+    /// The newest MySQL version the release can stream from, i.e. `8.0`
+    pub max_mysql: &'static str,
+}
+
+pub const DEBEZIUM_17: DebeziumVersion = DebeziumVersion {
+    version: "1.7",
+    plugin_version: "1.7.0",
+    worker: "kafka-connect-17",
+    host_port: 28084,
+    source: PluginSource::ConfluentHub,
+    max_mysql: "8.0",
+};
+
+pub const DEBEZIUM_24: DebeziumVersion = DebeziumVersion {
+    version: "2.4",
+    plugin_version: "2.4.2",
+    worker: KAFKA_CONNECT,
+    host_port: 28083,
+    source: PluginSource::ConfluentHub,
+    max_mysql: "8.0",
+};
+
+/// MySQL 8.4 removed `SHOW MASTER STATUS`; 2.7 is the first release that streams from it
+pub const DEBEZIUM_27: DebeziumVersion = DebeziumVersion {
+    version: "2.7",
+    plugin_version: "2.7.3.Final",
+    worker: "kafka-connect-27",
+    host_port: 28085,
+    source: PluginSource::Maven,
+    max_mysql: "8.4",
+};
+
+/// Every Debezium release brought up by the stack
+pub const DEBEZIUM_VERSIONS: [DebeziumVersion; 3] = [DEBEZIUM_24, DEBEZIUM_17, DEBEZIUM_27];
+
+/// The release used when a testbed request doesn't pick one
+pub const DEFAULT_DEBEZIUM: DebeziumVersion = DEBEZIUM_24;
+
+impl DebeziumVersion {
+    /// Finds the release for the specified version, i.e. `2.4`
+    pub fn for_version(version: &str) -> Option<DebeziumVersion> {
+        DEBEZIUM_VERSIONS.into_iter().find(|d| d.version == version)
+    }
+
+    /// Debezium 2.0 renamed `database.server.name` to `topic.prefix` and `database.history.*` to
+    /// `schema.history.internal.*`
+    pub fn is_legacy(&self) -> bool {
+        self.version.starts_with("1.")
+    }
+
+    /// True when the release can stream from the MySQL server
+    pub fn supports(&self, server: &MySqlServer) -> bool {
+        version_number(server.version) <= version_number(self.max_mysql)
+    }
+
+    /// The shell command that installs the release's plugin in its worker
+    pub fn install_command(&self) -> String {
+        match self.source {
+            PluginSource::ConfluentHub => {
+                format!("confluent-hub install --no-prompt debezium/debezium-connector-mysql:{}", self.plugin_version)
+            }
+            PluginSource::Maven => format!(
+                "mkdir -p {PLUGIN_DIR} && curl -sfL https://repo1.maven.org/maven2/io/debezium/debezium-connector-mysql/{v}/debezium-connector-mysql-{v}-plugin.tar.gz | tar -xz -C {PLUGIN_DIR}",
+                v = self.plugin_version
+            ),
+        }
+    }
+
+    /// The worker's REST API, as published on the host
+    pub fn url(&self) -> String {
+        format!("http://localhost:{}", self.host_port)
+    }
+
+    /// The worker's REST API on the `testbed` network
+    pub fn internal_url(&self) -> String {
+        format!("http://{}:8083", self.worker)
+    }
+}
+
+/// Parses a `major.minor` version, so `8.4` sorts after `8.0` and `10.0` after `8.4`
+fn version_number(version: &str) -> (u32, u32) {
+    let mut parts = version.split('.').map(|p| p.parse().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+pub async fn get_kafka_connect(runtime: &dyn ContainerRuntime, debezium: &DebeziumVersion) -> Result<String> {
     let binding = PortBinding {
         host_ip: None,
-        host_port: Some("28083".to_string()),
+        host_port: Some(debezium.host_port.to_string()),
     };
     let mut portmap = PortMap::new();
     portmap.insert("8083/tcp".to_string(), vec![binding].into());
 
-    info!("Setup kafka-connect");
+    // every worker is its own Connect cluster, with its own group and internal topics
+    let worker = debezium.worker;
+    let env = vec![
+        "CONNECT_BOOTSTRAP_SERVERS=localhost:29092,kafka:9092".to_string(),
+        "CONNECT_REST_PORT=8083".to_string(),
+        format!("CONNECT_GROUP_ID={worker}"),
+        format!("CONNECT_CONFIG_STORAGE_TOPIC=_{worker}-configs"),
+        format!("CONNECT_OFFSET_STORAGE_TOPIC=_{worker}-offsets"),
+        format!("CONNECT_STATUS_STORAGE_TOPIC=_{worker}-status"),
+        // keep in step with DEFAULT_KEY_CONVERTER and DEFAULT_VALUE_CONVERTER; testbeds override these per connector
+        "CONNECT_KEY_CONVERTER=org.apache.kafka.connect.storage.StringConverter".to_string(),
+        "CONNECT_VALUE_CONVERTER=io.confluent.connect.avro.AvroConverter".to_string(),
+        "CONNECT_VALUE_CONVERTER_SCHEMA_REGISTRY_URL=http://schema-registry:8081".to_string(),
+        format!("CONNECT_REST_ADVERTISED_HOST_NAME={worker}"),
+        "CONNECT_CONFIG_STORAGE_REPLICATION_FACTOR=1".to_string(),
+        "CONNECT_OFFSET_STORAGE_REPLICATION_FACTOR=1".to_string(),
+        "CONNECT_STATUS_STORAGE_REPLICATION_FACTOR=1".to_string(),
+        // flush offsets often, so they show the progress of incremental snapshots
+        "CONNECT_OFFSET_FLUSH_INTERVAL_MS=1000".to_string(),
+        "CONNECT_PLUGIN_PATH=/usr/share/java,/usr/share/confluent-hub-components,/data/connect-jars".to_string(),
+    ];
+
+    info!("Setup {worker}");
     let connect_config = Config {
-        image: Some(KAFKA_CONNECT_IMAGE),
-        env: Some(vec![
-      "CONNECT_BOOTSTRAP_SERVERS=localhost:29092,kafka:9092",
-      "CONNECT_REST_PORT=8083",
-      "CONNECT_GROUP_ID=kafka-connect",
-      "CONNECT_CONFIG_STORAGE_TOPIC=_connect-configs",
-      "CONNECT_OFFSET_STORAGE_TOPIC=_connect-offsets",
-      "CONNECT_STATUS_STORAGE_TOPIC=_connect-status",
-      "CONNECT_KEY_CONVERTER=org.apache.kafka.connect.storage.StringConverter",
-      "CONNECT_VALUE_CONVERTER=io.confluent.connect.avro.AvroConverter",
-      "CONNECT_VALUE_CONVERTER_SCHEMA_REGISTRY_URL=http://schema-registry:8081",
-      "CONNECT_REST_ADVERTISED_HOST_NAME=kafka-connect",
-      "CONNECT_CONFIG_STORAGE_REPLICATION_FACTOR=1",
-      "CONNECT_OFFSET_STORAGE_REPLICATION_FACTOR=1",
-      "CONNECT_STATUS_STORAGE_REPLICATION_FACTOR=1",
-      "CONNECT_PLUGIN_PATH: /usr/share/java,/usr/share/confluent-hub-components,/data/connect-jars"
-         ]),
+        image: Some(KAFKA_CONNECT_IMAGE.to_string()),
+        env: Some(env),
         host_config: Some(HostConfig {
             network_mode: Some(String::from(NETWORK)),
             port_bindings: Some(portmap),
@@ -61,17 +161,18 @@ pub async fn get_kafka_connect(docker: &Docker) -> Result<ContainerCreateRespons
         ..Default::default()
     };
 
-    info!("Creating kakfa-connect container");
-    let container = &docker
-        .create_container(
-            Some(CreateContainerOptions {
-                name: KAFKA_CONNECT,
-                platform: None,
-            }),
-            connect_config,
-        )
-        .await?;
-
-    Ok(container.clone())
+    info!("Creating {worker} container");
+    runtime.create(worker, connect_config).await
 }
 
+#[test]
+fn test_supports() {
+    use crate::stack::containers::mysql::{MYSQL_57, MYSQL_80, MYSQL_84};
+
+    assert!(DEBEZIUM_17.supports(&MYSQL_57));
+    assert!(DEBEZIUM_24.supports(&MYSQL_80));
+    assert!(!DEBEZIUM_24.supports(&MYSQL_84));
+    assert!(!DEBEZIUM_17.supports(&MYSQL_84));
+    assert!(DEBEZIUM_27.supports(&MYSQL_84));
+    assert!(version_number("10.0") > version_number("8.4"));
+}
@@ -1,33 +1,17 @@
-use bollard::models::ContainerCreateResponse;
-use bollard::Docker;
-use bollard::image::CreateImageOptions;
 use anyhow::Result;
 use bollard::models::HostConfig;
 use crate::stack::containers::NETWORK;
 use bollard::container::Config;
-use futures_util::TryStreamExt;
 use tracing::info;
-use bollard::container::CreateContainerOptions;
+use crate::stack::runtime::ContainerRuntime;
 use bollard::models::{PortBinding, PortMap};
+use crate::stack::containers::kafka_connect::DEBEZIUM_VERSIONS;
 
 
 pub const KUI: &str = "kui";
 pub const KUI_IMAGE: &str = "provectuslabs/kafka-ui:latest";
 
-pub async fn get_kui(docker: &Docker) -> Result<ContainerCreateResponse> {
-    info!("Getting kui image");
-    let _ = &docker
-        .create_image(
-            Some(CreateImageOptions {
-                from_image: KUI_IMAGE,
-                ..Default::default()
-            }),
-            None,
-            None,
-        )
-        .try_collect::<Vec<_>>()
-        .await?;
-
+pub async fn get_kui(runtime: &dyn ContainerRuntime) -> Result<String> {
     // exposed ports
     // An object mapping ports to an empty object in the form:  `{\"<port>/<tcp|udp|sctp>\": {}}`
     // expected struct `std::collections::HashMap<&str, std::collections::HashMap<(), ()>>`
@@ -45,18 +29,24 @@ pub async fn get_kui(docker: &Docker) -> Result<ContainerCreateResponse> {
     let mut portmap = PortMap::new();
     portmap.insert("8080/tcp".to_string(), vec![binding].into());
 
+    let mut env: Vec<String> = vec![
+        "KAFKA_CLUSTERS_0_NAME=testbed".to_string(),
+        "KAFKA_CLUSTERS_0_BOOTSTRAPSERVERS=kafka:9092".to_string(),
+        "KAFKA_CLUSTERS_0_SCHEMAREGISTRY=http://schema-registry:8081".to_string(),
+        //KAFKA_CLUSTERS_0_KSQLDBSERVER: http://ksqldb-server-1:8088
+        "DYNAMIC_CONFIG_ENABLED=true".to_string(),
+        "SERVER_PORT=8080".to_string(),
+    ];
+
+    // one Connect cluster per Debezium release
+    for (i, debezium) in DEBEZIUM_VERSIONS.iter().enumerate() {
+        env.push(format!("KAFKA_CLUSTERS_0_KAFKACONNECT_{i}_NAME=debezium-{}", debezium.version));
+        env.push(format!("KAFKA_CLUSTERS_0_KAFKACONNECT_{i}_ADDRESS={}", debezium.internal_url()));
+    }
+
     let kui_config = Config {
-        image: Some(KUI_IMAGE),
-        env: Some(vec![
-            "KAFKA_CLUSTERS_0_NAME=testbed",
-            "KAFKA_CLUSTERS_0_BOOTSTRAPSERVERS=kafka:9092",
-            "KAFKA_CLUSTERS_0_SCHEMAREGISTRY=http://schema-registry:8081",
-            "KAFKA_CLUSTERS_0_KAFKACONNECT_0_NAME=connect-cluster",
-            "KAFKA_CLUSTERS_0_KAFKACONNECT_0_ADDRESS=http://kafka-connect:8083",
-            //KAFKA_CLUSTERS_0_KSQLDBSERVER: http://ksqldb-server-1:8088
-            "DYNAMIC_CONFIG_ENABLED=true",
-            "SERVER_PORT=8080",
-        ]),
+        image: Some(KUI_IMAGE.to_string()),
+        env: Some(env),
         host_config: Some(HostConfig {
             network_mode: Some(String::from(NETWORK)),
             //publish_all_ports: Some(true),
@@ -68,15 +58,5 @@ pub async fn get_kui(docker: &Docker) -> Result<ContainerCreateResponse> {
     };
 
     info!("Creating kui container");
-    let container = &docker
-        .create_container(
-            Some(CreateContainerOptions {
-                name: KUI,
-                platform: None,
-            }),
-            kui_config,
-        )
-        .await?;
-
-    Ok(container.clone())
+    runtime.create(KUI, kui_config).await
 }
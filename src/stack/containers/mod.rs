@@ -1,6 +1,5 @@
-use bollard::Docker;
-use bollard::network::CreateNetworkOptions;
 use tracing::error;
+use crate::stack::runtime::ContainerRuntime;
 
 
 pub mod mysql;
@@ -13,15 +12,8 @@ pub mod schema_registry;
 pub const NETWORK: &str = "testbed";
 
 
-pub async fn create_testbed_network(docker: &Docker) {
-    let config = CreateNetworkOptions {
-        name: NETWORK,
-        check_duplicate: true,
-        internal: false,
-        ..Default::default()
-    };
-
-    if let Err(e) = docker.create_network(config).await {
+pub async fn create_testbed_network(runtime: &dyn ContainerRuntime) {
+    if let Err(e) = runtime.create_network(NETWORK).await {
         error!("{e:?}");
     }
-}
\ No newline at end of file
+}
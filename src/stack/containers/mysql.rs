@@ -1,89 +1,111 @@
-use bollard::models::ContainerCreateResponse;
-use bollard::Docker;
-use bollard::image::CreateImageOptions;
 use anyhow::Result;
 use bollard::models::HostConfig;
 use crate::stack::containers::NETWORK;
 use bollard::container::Config;
-use futures_util::TryStreamExt;
 use tracing::info;
-use bollard::container::CreateContainerOptions;
+use crate::stack::runtime::ContainerRuntime;
 use bollard::models::{PortBinding, PortMap, Mount, MountTypeEnum};
-use std::{env, path::PathBuf};
+use crate::stack::templates::get_templates;
 
 
-pub const MYSQL_IMAGE: &str = "mysql:8.0";
-pub const MYSQL: &str = "db";
 pub const MYSQL_INIT_PATH: &str = "/docker-entrypoint-initdb.d";
-//const MYSQL_LOCAL_INIT_PATH: &str = "sql_init";
 
-fn get_sql_init_dir() -> PathBuf {
-    let path = env::current_dir().unwrap();
-    path.join("sql_init")
+/// A MySQL server in the stack. Each server runs in its own container, which is also
+/// its hostname on the `testbed` network, and is published on its own host port
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MySqlServer {
+    /// The version a testbed request uses to pick this server, i.e. `5.7`
+    pub version: &'static str,
+    /// The container name and hostname on the `testbed` network
+    pub name: &'static str,
+    pub image: &'static str,
+    pub host_port: u16,
+    /// Extra arguments passed to `mysqld`
+    pub args: &'static [&'static str],
 }
 
+pub const MYSQL_57: MySqlServer = MySqlServer {
+    version: "5.7",
+    name: "db57",
+    image: "mysql:5.7",
+    host_port: 23307,
+    // binlog is off by default prior to 8.0
+    args: &["--server-id=1", "--log-bin=mysql-bin", "--binlog-format=ROW"],
+};
 
-pub async fn get_mysql(docker: &Docker) -> Result<ContainerCreateResponse> {
-    info!("Getting db image");
-    let _ = &docker
-        .create_image(
-            Some(CreateImageOptions {
-                from_image: MYSQL_IMAGE,
-                ..Default::default()
-            }),
-            None,
-            None,
-        )
-        .try_collect::<Vec<_>>()
-        .await?;
+pub const MYSQL_80: MySqlServer = MySqlServer {
+    version: "8.0",
+    name: "db",
+    image: "mysql:8.0",
+    host_port: 23306,
+    args: &[],
+};
+
+pub const MYSQL_84: MySqlServer = MySqlServer {
+    version: "8.4",
+    name: "db84",
+    image: "mysql:8.4",
+    host_port: 23308,
+    args: &[],
+};
+
+/// Every MySQL server brought up by the stack
+pub const MYSQL_SERVERS: [MySqlServer; 3] = [MYSQL_80, MYSQL_57, MYSQL_84];
+
+/// The server used when a testbed request doesn't pick one
+pub const DEFAULT_MYSQL_SERVER: MySqlServer = MYSQL_80;
+
+impl MySqlServer {
+    /// Finds the server for the specified version, i.e. `8.4`
+    pub fn for_version(version: &str) -> Option<MySqlServer> {
+        MYSQL_SERVERS.into_iter().find(|s| s.version == version)
+    }
+}
 
+pub async fn get_mysql(runtime: &dyn ContainerRuntime, server: &MySqlServer) -> Result<String> {
     let binding = PortBinding {
         host_ip: None,
-        host_port: Some("23306".to_string()),
+        host_port: Some(server.host_port.to_string()),
     };
 
     let mut portmap = PortMap::new();
     portmap.insert("3306/tcp".to_string(), vec![binding].into());
 
-    let init_dir = get_sql_init_dir();
-    let init_dir = init_dir.into_os_string().into_string().unwrap();
-    //let init_dir = init_dir.replace('\"', "");
-    //let init_dir = MYSQL_LOCAL_INIT_PATH.to_string();
+    // each template's init script is mounted as its own file, so the entrypoint loads every
+    // template database on first start
+    let mounts = get_templates()
+        .iter()
+        .map(|template| {
+            let init_sql = template.init_sql().into_os_string().into_string().unwrap();
+            info!("Init script: {init_sql:?}");
 
-    info!("Init dir: {init_dir:?}");
+            Mount {
+                typ: Some(MountTypeEnum::BIND),
+                source: Some(init_sql),
+                target: Some(format!("{}/{}.sql", MYSQL_INIT_PATH, template.name)),
+                ..Default::default()
+            }
+        })
+        .collect();
 
     let db_config = Config {
-        image: Some(MYSQL_IMAGE),
+        image: Some(server.image.to_string()),
+        cmd: if server.args.is_empty() { None } else { Some(server.args.iter().map(|a| a.to_string()).collect()) },
         env: Some(vec![
-            "MYSQL_USER=testbed",
-            "MYSQL_PASSWORD=testbed",
-            "MYSQL_DATABASE=testbed",
-            "MYSQL_ROOT_PASSWORD=testbed",
+            "MYSQL_USER=testbed".to_string(),
+            "MYSQL_PASSWORD=testbed".to_string(),
+            "MYSQL_DATABASE=testbed".to_string(),
+            "MYSQL_ROOT_PASSWORD=testbed".to_string(),
         ]),
         host_config: Some(HostConfig {
             network_mode: Some(String::from(NETWORK)),
             port_bindings: Some(portmap),
-            mounts: Some(vec![Mount {
-                typ: Some(MountTypeEnum::BIND),
-                source: Some(init_dir),
-                target: Some(String::from(MYSQL_INIT_PATH)),
-                ..Default::default()
-            }]),
+            mounts: Some(mounts),
             ..Default::default()
         }),
         ..Default::default()
     };
 
-    info!("Creating db container");
-    let container = &docker
-        .create_container(
-            Some(CreateContainerOptions {
-                name: MYSQL,
-                platform: None,
-            }),
-            db_config,
-        )
-        .await?;
-
-    Ok(container.clone())
+    info!("Creating {} container", server.name);
+    runtime.create(server.name, db_config).await
 }
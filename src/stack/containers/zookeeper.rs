@@ -1,38 +1,20 @@
-
-use bollard::models::ContainerCreateResponse;
-use bollard::Docker;
-use bollard::image::CreateImageOptions;
 use anyhow::Result;
 use bollard::models::HostConfig;
 use bollard::container::Config;
-use futures_util::TryStreamExt;
 use tracing::info;
-use bollard::container::CreateContainerOptions;
 use crate::stack::containers::NETWORK;
+use crate::stack::runtime::ContainerRuntime;
 
 pub const ZOOKEEPER: &str = "zookeeper";
 pub const ZOOKEEPER_IMAGE: &str = "confluentinc/cp-zookeeper:latest";
 
-pub async fn get_zookeeper(docker: &Docker) -> Result<ContainerCreateResponse> {
-    info!("Getting zookeeper image");
-    let _ = &docker
-        .create_image(
-            Some(CreateImageOptions {
-                from_image: ZOOKEEPER_IMAGE,
-                ..Default::default()
-            }),
-            None,
-            None,
-        )
-        .try_collect::<Vec<_>>()
-        .await?;
-
+pub async fn get_zookeeper(runtime: &dyn ContainerRuntime) -> Result<String> {
     let zookeeper_config = Config {
-        image: Some(ZOOKEEPER_IMAGE),
+        image: Some(ZOOKEEPER_IMAGE.to_string()),
         env: Some(vec![
-            "ZOOKEEPER_CLIENT_PORT=2181",
-            "ZOOKEEPER_TICK_TIME=2000",
-            "ZOOKEEPER_SYNC_LIMIT=2",
+            "ZOOKEEPER_CLIENT_PORT=2181".to_string(),
+            "ZOOKEEPER_TICK_TIME=2000".to_string(),
+            "ZOOKEEPER_SYNC_LIMIT=2".to_string(),
         ]),
         host_config: Some(HostConfig {
             network_mode: Some(String::from(NETWORK)),
@@ -42,15 +24,5 @@ pub async fn get_zookeeper(docker: &Docker) -> Result<ContainerCreateResponse> {
     };
 
     info!("Creating zookeeper container");
-    let container = &docker
-        .create_container(
-            Some(CreateContainerOptions {
-                name: ZOOKEEPER,
-                platform: None,
-            }),
-            zookeeper_config,
-        )
-        .await?;
-
-    Ok(container.clone())
+    runtime.create(ZOOKEEPER, zookeeper_config).await
 }
@@ -0,0 +1,57 @@
+use crate::stack::connector::ConnectorConfig;
+use crate::stack::containers::schema_registry::REGISTRY;
+
+pub use debezium_testbed_client::types::Converter;
+
+/// The key converter of the Kafka Connect worker, used unless a testbed overrides it
+pub const DEFAULT_KEY_CONVERTER: Converter = Converter::String;
+/// The value converter of the Kafka Connect worker, used unless a testbed overrides it
+pub const DEFAULT_VALUE_CONVERTER: Converter = Converter::Avro;
+
+/// Returns the connector properties overriding the worker's converter, where `prefix` is
+/// `key.converter` or `value.converter`
+pub fn converter_properties(converter: Converter, prefix: &str) -> Vec<(String, String)> {
+    let mut properties = vec![(prefix.to_string(), converter.class().to_string())];
+
+    if converter.uses_registry() {
+        properties.push((
+            format!("{prefix}.schema.registry.url"),
+            format!("http://{REGISTRY}:8081"),
+        ));
+    }
+
+    if converter == Converter::Json {
+        properties.push((format!("{prefix}.schemas.enable"), "true".to_string()));
+    }
+
+    properties
+}
+
+/// Overrides the worker's key and value converters in the connector config
+pub fn set_converters(config: &mut ConnectorConfig, key: Converter, value: Converter) {
+    let properties = converter_properties(key, "key.converter")
+        .into_iter()
+        .chain(converter_properties(value, "value.converter"));
+
+    for (name, value) in properties {
+        config.set(&name, value);
+    }
+}
+
+#[test]
+fn test_converter_properties() {
+    assert_eq!(
+        converter_properties(Converter::Protobuf, "value.converter"),
+        vec![
+            ("value.converter".to_string(), "io.confluent.connect.protobuf.ProtobufConverter".to_string()),
+            ("value.converter.schema.registry.url".to_string(), "http://schema-registry:8081".to_string()),
+        ]
+    );
+    assert_eq!(
+        converter_properties(Converter::Json, "key.converter"),
+        vec![
+            ("key.converter".to_string(), "org.apache.kafka.connect.json.JsonConverter".to_string()),
+            ("key.converter.schemas.enable".to_string(), "true".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+use crate::stack::kafka_connect::ConfigError;
+
+/// Errors for a testbed request that the caller can fix, as opposed to failures in the stack itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestBedError {
+    /// No MySQL server in the stack runs the requested version
+    UnknownServer(String),
+    /// No Connect worker in the stack runs the requested Debezium version
+    UnknownDebeziumVersion(String),
+    /// The requested Debezium release can't stream from the requested MySQL server
+    UnsupportedServer { server: String, debezium: String },
+    /// There's no directory in `templates/` for the requested template
+    UnknownTemplate(String),
+    /// The template has no such fixture set, given as `<template>/<set>`
+    UnknownFixtures(String),
+    /// There's no testbed with the name
+    UnknownTestBed(String),
+    /// The testbed database has no such table
+    UnknownTable(String),
+    /// The testbed has no such registry subject or schema version
+    UnknownSubject(String),
+    /// Kafka Connect rejected the rendered connector config
+    InvalidConnectorConfig(Vec<ConfigError>),
+    /// MySQL rejected SQL run against a testbed database
+    InvalidSql(String),
+}
+
+impl fmt::Display for TestBedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestBedError::UnknownServer(version) => {
+                write!(f, "no MySQL server in the stack runs version: {version}")
+            }
+            TestBedError::UnknownDebeziumVersion(version) => {
+                write!(f, "no Connect worker in the stack runs Debezium version: {version}")
+            }
+            TestBedError::UnsupportedServer { server, debezium } => {
+                write!(f, "Debezium {debezium} can't stream from MySQL {server}; pick a newer Debezium release")
+            }
+            TestBedError::UnknownTemplate(name) => write!(f, "no such template: {name}"),
+            TestBedError::UnknownFixtures(name) => write!(f, "no such fixtures: {name}"),
+            TestBedError::UnknownTestBed(name) => write!(f, "no such testbed: {name}"),
+            TestBedError::UnknownTable(name) => write!(f, "no such table: {name}"),
+            TestBedError::UnknownSubject(name) => write!(f, "no such subject: {name}"),
+            TestBedError::InvalidConnectorConfig(errors) => {
+                write!(f, "invalid connector config:")?;
+                for e in errors {
+                    write!(f, " {}: {};", e.property, e.errors.join(", "))?;
+                }
+                Ok(())
+            }
+            TestBedError::InvalidSql(message) => write!(f, "invalid sql: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for TestBedError {}
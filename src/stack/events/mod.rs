@@ -0,0 +1,270 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use apache_avro::{from_avro_datum, Schema};
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::error::KafkaError;
+use rdkafka::message::{Headers, Message};
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
+use schema_registry_converter::async_impl::proto_decoder::ProtoDecoder;
+use schema_registry_converter::async_impl::schema_registry::SrSettings;
+use serde_json::Value;
+
+use crate::stack::converters::Converter;
+use crate::stack::kafka_admin::KAFKA_BROKER_URL;
+use crate::stack::schema_registry::SchemaRegistryClient;
+
+use debezium_testbed_client::protobuf;
+
+pub use debezium_testbed_client::types::{EventHeader, EventPage, StartPosition, TopicEvent};
+
+/// The number of events in a page unless the query asks for fewer
+pub const DEFAULT_EVENT_LIMIT: usize = 100;
+/// The most events in one page
+pub const MAX_EVENT_LIMIT: usize = 1000;
+
+/// How long to read for before returning a short page
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const METADATA_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A record as read from the topic, before it's decoded
+struct RawRecord {
+    partition: i32,
+    offset: i64,
+    timestamp: Option<i64>,
+    key: Option<Vec<u8>>,
+    value: Option<Vec<u8>>,
+    headers: Vec<EventHeader>,
+}
+
+/// Reads up to `limit` events from the topic, starting at the position, and decodes their keys and
+/// values with the converters they were written with
+pub async fn read_events(
+    topic: &str,
+    start: StartPosition,
+    limit: usize,
+    key_converter: Converter,
+    value_converter: Converter,
+) -> Result<EventPage> {
+    let read_topic = topic.to_string();
+    let (records, next) = tokio::task::spawn_blocking(move || read_records(&read_topic, &start, limit)).await??;
+
+    let mut decoder = Decoder::new(SchemaRegistryClient::default());
+    let mut events = vec![];
+    for record in records {
+        events.push(TopicEvent {
+            partition: record.partition,
+            offset: record.offset,
+            timestamp: record.timestamp,
+            key: decoder.decode(key_converter, record.key.as_deref()).await?,
+            value: decoder.decode(value_converter, record.value.as_deref()).await?,
+            headers: record.headers,
+        });
+    }
+
+    let next = next
+        .iter()
+        .map(|(partition, offset)| format!("{partition}:{offset}"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Ok(EventPage {
+        topic: topic.to_string(),
+        events,
+        next,
+    })
+}
+
+/// Reads the records with a consumer that's assigned the topic's partitions directly, so no group
+/// offsets are committed. Returns the records and the next offset in each partition
+fn read_records(topic: &str, start: &StartPosition, limit: usize) -> Result<(Vec<RawRecord>, BTreeMap<i32, i64>)> {
+    let consumer: BaseConsumer = ClientConfig::new()
+        .set("bootstrap.servers", KAFKA_BROKER_URL)
+        .set("group.id", format!("testbed-events-{}", rand::random::<u32>()))
+        .set("enable.auto.commit", "false")
+        .set("enable.partition.eof", "true")
+        .create()?;
+
+    let metadata = consumer.fetch_metadata(Some(topic), METADATA_TIMEOUT)?;
+    let partitions: Vec<i32> = match metadata.topics().first() {
+        Some(t) if t.error().is_none() => t.partitions().iter().map(|p| p.id()).collect(),
+        // the connector hasn't written to the topic yet
+        _ => return Ok((vec![], BTreeMap::new())),
+    };
+
+    let mut next = BTreeMap::new();
+    let mut at_end = BTreeMap::new();
+    for partition in &partitions {
+        let (low, high) = consumer.fetch_watermarks(topic, *partition, METADATA_TIMEOUT)?;
+        let offset = match start {
+            StartPosition::Earliest | StartPosition::Timestamp(_) => low,
+            StartPosition::Latest => high,
+            StartPosition::Offset(offset) => (*offset).clamp(low, high),
+            StartPosition::Partitions(offsets) => offsets.get(partition).copied().unwrap_or(low).clamp(low, high),
+        };
+        next.insert(*partition, offset);
+        at_end.insert(*partition, offset >= high);
+    }
+
+    if let StartPosition::Timestamp(timestamp) = start {
+        let mut query = TopicPartitionList::new();
+        for partition in &partitions {
+            query.add_partition_offset(topic, *partition, Offset::Offset(*timestamp))?;
+        }
+        for element in consumer.offsets_for_times(query, METADATA_TIMEOUT)?.elements() {
+            let (_, high) = consumer.fetch_watermarks(topic, element.partition(), METADATA_TIMEOUT)?;
+            // no events at or after the timestamp
+            let offset = element.offset().to_raw().filter(|o| *o >= 0).unwrap_or(high);
+            next.insert(element.partition(), offset);
+            at_end.insert(element.partition(), offset >= high);
+        }
+    }
+
+    let mut assignment = TopicPartitionList::new();
+    for (partition, offset) in &next {
+        assignment.add_partition_offset(topic, *partition, Offset::Offset(*offset))?;
+    }
+    consumer.assign(&assignment)?;
+
+    let mut records = vec![];
+    let deadline = Instant::now() + READ_TIMEOUT;
+    while records.len() < limit && at_end.values().any(|end| !end) && Instant::now() < deadline {
+        match consumer.poll(Duration::from_millis(500)) {
+            Some(Ok(message)) => {
+                next.insert(message.partition(), message.offset() + 1);
+                records.push(RawRecord {
+                    partition: message.partition(),
+                    offset: message.offset(),
+                    timestamp: message.timestamp().to_millis(),
+                    key: message.key().map(<[u8]>::to_vec),
+                    value: message.payload().map(<[u8]>::to_vec),
+                    headers: message
+                        .headers()
+                        .map(|headers| {
+                            headers
+                                .iter()
+                                .map(|h| EventHeader {
+                                    key: h.key.to_string(),
+                                    value: h.value.map(|v| String::from_utf8_lossy(v).into_owned()),
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                });
+            }
+            Some(Err(KafkaError::PartitionEOF(partition))) => {
+                at_end.insert(partition, true);
+            }
+            Some(Err(e)) => return Err(e.into()),
+            None => {}
+        }
+    }
+
+    Ok((records, next))
+}
+
+/// Decodes keys and values, caching the registry's schemas by id
+struct Decoder {
+    schema_registry: SchemaRegistryClient,
+    schemas: HashMap<u32, Schema>,
+    /// Resolves the message in the registry's `.proto` schema, and caches the schemas itself
+    proto: ProtoDecoder<'static>,
+}
+
+impl Decoder {
+    fn new(schema_registry: SchemaRegistryClient) -> Self {
+        let proto = ProtoDecoder::new(SrSettings::new(schema_registry.url().to_string()));
+        Decoder {
+            schema_registry,
+            schemas: HashMap::new(),
+            proto,
+        }
+    }
+
+    async fn decode(&mut self, converter: Converter, data: Option<&[u8]>) -> Result<Value> {
+        let Some(data) = data else {
+            return Ok(Value::Null);
+        };
+
+        match converter {
+            Converter::String => Ok(Value::String(String::from_utf8_lossy(data).into_owned())),
+            Converter::Json => Ok(serde_json::from_slice(data)?),
+            Converter::JsonSchema => Ok(serde_json::from_slice(split_wire_header(data)?.1)?),
+            Converter::Avro => {
+                let (id, data) = split_wire_header(data)?;
+                if !self.schemas.contains_key(&id) {
+                    let schema = self.schema_registry.schema_by_id(id).await?;
+                    self.schemas.insert(id, Schema::parse_str(&schema.schema)?);
+                }
+                decode_avro(&self.schemas[&id], data)
+            }
+            Converter::Protobuf => {
+                let decoded = self
+                    .proto
+                    .decode_with_context(Some(data))
+                    .await?
+                    .ok_or_else(|| anyhow!("not in the schema registry wire format"))?;
+                Ok(protobuf::to_json(&decoded.value, &decoded.context.context))
+            }
+        }
+    }
+}
+
+/// Decodes Avro's binary encoding with its writer schema, then converts it to JSON. Unions are
+/// converted to the value of the branch that was written
+fn decode_avro(schema: &Schema, mut data: &[u8]) -> Result<Value> {
+    let value = from_avro_datum(schema, &mut data, None)?;
+    Ok(Value::try_from(value)?)
+}
+
+/// Splits the registry's wire format into the schema id and the encoded data: a zero magic byte,
+/// then the 4 byte big endian schema id
+fn split_wire_header(data: &[u8]) -> Result<(u32, &[u8])> {
+    match data {
+        [0, a, b, c, d, rest @ ..] => Ok((u32::from_be_bytes([*a, *b, *c, *d]), rest)),
+        _ => Err(anyhow!("not in the schema registry wire format")),
+    }
+}
+
+#[test]
+fn test_split_wire_header() {
+    let (id, data) = split_wire_header(&[0, 0, 0, 1, 2, b'{', b'}']).unwrap();
+    assert_eq!(id, 258);
+    assert_eq!(data, b"{}");
+    assert!(split_wire_header(b"{}").is_err());
+}
+
+#[test]
+fn test_decode_debezium_envelope() {
+    let schema = Schema::parse_str(
+        r#"{"type":"record","name":"Envelope","namespace":"demo_1.demo_1.example","fields":[
+            {"name":"before","type":["null",{"type":"record","name":"Value","fields":[
+                {"name":"id","type":"int"},
+                {"name":"note","type":["null","string"],"default":null}]}],"default":null},
+            {"name":"after","type":["null","Value"],"default":null},
+            {"name":"op","type":"string"},
+            {"name":"ts_ms","type":["null","long"],"default":null},
+            {"name":"tags","type":{"type":"array","items":"string"}}]}"#,
+    )
+    .unwrap();
+
+    let data = [
+        0x00, // before: null
+        0x02, 0x54, 0x02, 0x04, b'h', b'i', // after: {id: 42, note: "hi"}
+        0x02, b'c', // op: "c"
+        0x02, 0x80, 0x01, // ts_ms: 64
+        0x01, 0x04, 0x02, b'a', 0x00, // tags: one block of -1 items, 2 bytes long
+    ];
+
+    assert_eq!(
+        decode_avro(&schema, &data).unwrap(),
+        serde_json::json!({
+            "before": null,
+            "after": { "id": 42, "note": "hi" },
+            "op": "c",
+            "ts_ms": 64,
+            "tags": ["a"]
+        })
+    );
+}
@@ -0,0 +1,180 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use anyhow::{anyhow, Result};
+use mysql::{prelude::Queryable, Value};
+use tracing::info;
+
+use crate::stack::containers::mysql::MySqlServer;
+use crate::stack::error::TestBedError;
+use crate::stack::mysql::get_mysql_pool;
+use crate::stack::templates::Template;
+
+pub use debezium_testbed_client::types::{FixtureOptions, FixturePhase};
+
+/// The value in a CSV fixture that is inserted as `NULL`, as in `LOAD DATA`
+const CSV_NULL: &str = "\\N";
+
+/// The rows for one table, each row being its column names and values
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixture {
+    pub table: String,
+    pub rows: Vec<Vec<(String, Value)>>,
+}
+
+/// Returns the directory of the fixture set in the template, i.e. `templates/orders/fixtures/default`
+pub fn get_fixtures_dir(template: &Template, set: &str) -> Result<PathBuf, TestBedError> {
+    let dir = template.dir.join("fixtures").join(set);
+    let is_valid = !set.is_empty() && set.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if is_valid && dir.is_dir() {
+        Ok(dir)
+    } else {
+        Err(TestBedError::UnknownFixtures(format!("{}/{}", template.name, set)))
+    }
+}
+
+/// Loads every fixture in the directory, sorted by table name. Each file is named after its table
+/// and is one of:
+///
+/// - `<table>.csv` with a header row of column names; `\N` is `NULL`
+/// - `<table>.json`, an array of objects
+/// - `<table>.yaml` or `<table>.yml`, a list of maps
+pub fn load_fixtures(dir: &Path) -> Result<Vec<Fixture>> {
+    let mut fixtures = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let (Some(table), Some(extension)) = (
+            path.file_stem().and_then(|s| s.to_str()),
+            path.extension().and_then(|s| s.to_str()),
+        ) else {
+            continue;
+        };
+
+        let rows = match extension {
+            "csv" => load_csv(&path)?,
+            "json" => load_json(serde_json::from_str(&fs::read_to_string(&path)?)?)?,
+            "yaml" | "yml" => load_json(serde_yaml::from_str(&fs::read_to_string(&path)?)?)?,
+            _ => continue,
+        };
+
+        fixtures.push(Fixture {
+            table: table.to_string(),
+            rows,
+        });
+    }
+
+    fixtures.sort_by(|a, b| a.table.cmp(&b.table));
+    Ok(fixtures)
+}
+
+fn load_csv(path: &Path) -> Result<Vec<Vec<(String, Value)>>> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
+
+    let mut rows = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let row = headers
+            .iter()
+            .zip(record.iter())
+            .map(|(column, value)| {
+                let value = match value {
+                    CSV_NULL => Value::NULL,
+                    v => Value::from(v),
+                };
+                (column.to_string(), value)
+            })
+            .collect();
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// JSON and YAML fixtures are both read as a list of JSON objects
+fn load_json(json: serde_json::Value) -> Result<Vec<Vec<(String, Value)>>> {
+    let serde_json::Value::Array(items) = json else {
+        return Err(anyhow!("fixture must be a list of rows"));
+    };
+
+    items
+        .into_iter()
+        .map(|item| match item {
+            serde_json::Value::Object(row) => Ok(row
+                .into_iter()
+                .map(|(column, value)| (column, json_to_mysql(value)))
+                .collect()),
+            other => Err(anyhow!("fixture row is not an object: {other}")),
+        })
+        .collect()
+}
+
+fn json_to_mysql(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::NULL,
+        serde_json::Value::Bool(b) => Value::from(b),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::from(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::from(u)
+            } else {
+                Value::from(n.as_f64().unwrap_or_default())
+            }
+        }
+        serde_json::Value::String(s) => Value::from(s),
+        // nested values are written to JSON columns as is
+        other => Value::from(other.to_string()),
+    }
+}
+
+/// Inserts the fixtures into the database, returning the number of rows inserted
+pub fn apply_fixtures(server: &MySqlServer, db_name: &str, fixtures: &[Fixture]) -> Result<usize> {
+    let pool = get_mysql_pool(server)?;
+    let mut conn = pool.get_conn()?;
+
+    // fixtures are applied in table name order, not in foreign key order
+    conn.query_drop("SET SESSION foreign_key_checks = 0")?;
+
+    let mut count = 0;
+    for fixture in fixtures {
+        info!("Applying {} rows to: {}.{}", fixture.rows.len(), db_name, fixture.table);
+
+        for row in &fixture.rows {
+            let columns = row
+                .iter()
+                .map(|(c, _)| format!("`{}`", c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let placeholders = vec!["?"; row.len()].join(", ");
+            let sql = format!(
+                "INSERT INTO `{}`.`{}` ({}) VALUES ({})",
+                db_name, fixture.table, columns, placeholders
+            );
+
+            let values: Vec<Value> = row.iter().map(|(_, v)| v.clone()).collect();
+            conn.exec_drop(sql, values)?;
+            count += 1;
+        }
+    }
+
+    conn.query_drop("SET SESSION foreign_key_checks = 1")?;
+
+    Ok(count)
+}
+
+#[test]
+fn test_load_fixtures() {
+    let template = crate::stack::templates::get_template("orders").unwrap();
+    let dir = get_fixtures_dir(&template, "default").unwrap();
+    let fixtures = load_fixtures(&dir).unwrap();
+
+    let tables: Vec<&str> = fixtures.iter().map(|f| f.table.as_str()).collect();
+    assert_eq!(tables, vec!["customers", "purchase_orders"]);
+    assert_eq!(fixtures[0].rows.len(), 3);
+    assert_eq!(fixtures[0].rows[0][1], ("name".to_string(), Value::from("Ada Lovelace")));
+    assert!(fixtures[1].rows[0].contains(&("id".to_string(), Value::from(1))));
+
+    assert!(get_fixtures_dir(&template, "../mydb").is_err());
+}
@@ -0,0 +1,651 @@
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use mysql::prelude::Queryable;
+use mysql::{Pool, PooledConn, Row, Value};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde_json::json;
+use tracing::{debug, info};
+
+use crate::stack::containers::mysql::MySqlServer;
+use crate::stack::error::TestBedError;
+use crate::stack::mysql::get_mysql_url;
+use crate::stack::signals::SIGNAL_TABLE;
+
+pub use debezium_testbed_client::types::{GenerateRequest, GenerateSummary};
+
+/// How many times a write is tried with new values when it collides with a unique key
+const UNIQUE_RETRIES: usize = 5;
+
+/// How many levels of parent rows are inserted for a foreign key that has nothing to reference
+const MAX_PARENT_DEPTH: usize = 3;
+
+/// The share of values in a nullable column, outside of any key, that are `NULL`
+const NULL_RATIO: f64 = 0.1;
+
+const ER_DUP_ENTRY: u16 = 1062;
+
+const WORDS: &[&str] = &[
+    "amber", "basin", "cedar", "delta", "ember", "fjord", "grove", "harbor", "island", "juniper",
+    "kestrel", "lagoon", "meadow", "nectar", "orchid", "prairie", "quartz", "river", "summit",
+    "tundra", "upland", "valley", "willow", "yarrow", "zephyr",
+];
+const FIRST_NAMES: &[&str] = &[
+    "Ada", "Alan", "Barbara", "Claude", "Edsger", "Frances", "Grace", "John", "Katherine",
+    "Ken", "Margaret", "Niklaus", "Radia", "Tim",
+];
+const LAST_NAMES: &[&str] = &[
+    "Allen", "Dijkstra", "Hamilton", "Hopper", "Johnson", "Kernighan", "Liskov", "Lovelace",
+    "McCarthy", "Perlman", "Ritchie", "Shannon", "Turing", "Wirth",
+];
+
+/// A column of a table, as described by `information_schema.COLUMNS`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub name: String,
+    /// i.e. `varchar`
+    pub data_type: String,
+    /// i.e. `varchar(64)`, `int unsigned` or `enum('new','paid')`
+    pub column_type: String,
+    pub nullable: bool,
+    /// The length of a string or binary column, in characters or bytes
+    pub max_length: Option<u64>,
+    /// The digits of a numeric column, or the bits of a `bit` column
+    pub precision: Option<u64>,
+    pub scale: Option<u64>,
+    /// `AUTO_INCREMENT` and generated columns are left to MySQL
+    pub generated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKey {
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+}
+
+/// A table and its keys. Foreign keys to tables in other databases are left out
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Column>,
+    pub primary_key: Vec<String>,
+    pub unique_keys: Vec<Vec<String>>,
+    pub foreign_keys: Vec<ForeignKey>,
+}
+
+impl Table {
+    fn is_key(&self, column: &str) -> bool {
+        self.primary_key.iter().any(|c| c == column) || self.unique_keys.iter().flatten().any(|c| c == column)
+    }
+
+    fn is_foreign_key(&self, column: &str) -> bool {
+        self.foreign_keys.iter().any(|fk| fk.columns.iter().any(|c| c == column))
+    }
+}
+
+/// A row of `information_schema.COLUMNS`: the table, column, data type, column type, nullability,
+/// length, precision, scale and extra
+type ColumnRow = (String, String, String, String, String, Option<u64>, Option<u64>, Option<u64>, String);
+
+/// A row of `information_schema.KEY_COLUMN_USAGE`: the table, constraint, column, and the
+/// referenced database, table and column of a foreign key
+type KeyRow = (String, String, String, Option<String>, Option<String>, Option<String>);
+
+/// Reads the tables of the database and their keys, without Debezium's signal table
+pub fn read_schema(conn: &mut PooledConn, db_name: &str) -> Result<Vec<Table>> {
+    let columns: Vec<ColumnRow> = conn.exec(
+        "SELECT c.TABLE_NAME, c.COLUMN_NAME, c.DATA_TYPE, c.COLUMN_TYPE, c.IS_NULLABLE, c.CHARACTER_MAXIMUM_LENGTH, \
+         c.NUMERIC_PRECISION, c.NUMERIC_SCALE, c.EXTRA \
+         FROM information_schema.COLUMNS c JOIN information_schema.TABLES t \
+         ON t.TABLE_SCHEMA = c.TABLE_SCHEMA AND t.TABLE_NAME = c.TABLE_NAME \
+         WHERE c.TABLE_SCHEMA = ? AND t.TABLE_TYPE = 'BASE TABLE' ORDER BY c.TABLE_NAME, c.ORDINAL_POSITION",
+        (db_name,),
+    )?;
+    let keys: Vec<KeyRow> = conn.exec(
+        "SELECT TABLE_NAME, CONSTRAINT_NAME, COLUMN_NAME, REFERENCED_TABLE_SCHEMA, REFERENCED_TABLE_NAME, REFERENCED_COLUMN_NAME \
+         FROM information_schema.KEY_COLUMN_USAGE WHERE TABLE_SCHEMA = ? ORDER BY TABLE_NAME, CONSTRAINT_NAME, ORDINAL_POSITION",
+        (db_name,),
+    )?;
+
+    Ok(build_tables(db_name, columns, keys))
+}
+
+fn build_tables(db_name: &str, columns: Vec<ColumnRow>, keys: Vec<KeyRow>) -> Vec<Table> {
+    let mut tables: BTreeMap<String, Table> = BTreeMap::new();
+
+    for (table, name, data_type, column_type, is_nullable, max_length, precision, scale, extra) in columns {
+        if table == SIGNAL_TABLE {
+            continue;
+        }
+        let extra = extra.to_lowercase();
+        tables
+            .entry(table.clone())
+            .or_insert_with(|| Table {
+                name: table,
+                ..Default::default()
+            })
+            .columns
+            .push(Column {
+                name,
+                data_type: data_type.to_lowercase(),
+                column_type: column_type.to_lowercase(),
+                nullable: is_nullable == "YES",
+                max_length,
+                precision,
+                scale,
+                // a column with an expression default is `DEFAULT_GENERATED` on 8.0, and is written like any other
+                generated: extra.contains("auto_increment")
+                    || extra.contains("virtual generated")
+                    || extra.contains("stored generated"),
+            });
+    }
+
+    // each constraint's columns are consecutive, in key order
+    let mut constraints: Vec<((String, String), Vec<KeyRow>)> = vec![];
+    for key in keys {
+        let id = (key.0.clone(), key.1.clone());
+        match constraints.last_mut() {
+            Some((last, rows)) if *last == id => rows.push(key),
+            _ => constraints.push((id, vec![key])),
+        }
+    }
+
+    for ((table, constraint), rows) in constraints {
+        let Some(table) = tables.get_mut(&table) else {
+            continue;
+        };
+        let columns: Vec<String> = rows.iter().map(|r| r.2.clone()).collect();
+
+        match &rows[0] {
+            _ if constraint == "PRIMARY" => table.primary_key = columns,
+            (_, _, _, Some(schema), Some(referenced_table), _) => {
+                if schema == db_name {
+                    table.foreign_keys.push(ForeignKey {
+                        columns,
+                        referenced_table: referenced_table.clone(),
+                        referenced_columns: rows.iter().filter_map(|r| r.5.clone()).collect(),
+                    });
+                }
+            }
+            _ => table.unique_keys.push(columns),
+        }
+    }
+
+    tables.into_values().collect()
+}
+
+/// Returns the values of an `enum` or `set` column type, i.e. `enum('new','it''s')`
+fn enum_values(column_type: &str) -> Vec<String> {
+    let Some(list) = column_type
+        .find('(')
+        .and_then(|start| column_type.rfind(')').map(|end| &column_type[start + 1..end]))
+    else {
+        return vec![];
+    };
+
+    let mut values = vec![];
+    let mut chars = list.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            continue;
+        }
+        let mut value = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\'' if chars.peek() == Some(&'\'') => {
+                    chars.next();
+                    value.push('\'');
+                }
+                '\'' => break,
+                c => value.push(c),
+            }
+        }
+        values.push(value);
+    }
+
+    values
+}
+
+/// Returns a random value that fits the column's type; types without a generator get `NULL`
+fn random_value(rng: &mut StdRng, column: &Column) -> Value {
+    let unsigned = column.column_type.contains("unsigned");
+    let int = |rng: &mut StdRng, signed_max: i64, unsigned_max: i64| {
+        Value::from(rng.gen_range(0..=if unsigned { unsigned_max } else { signed_max }))
+    };
+
+    match column.data_type.as_str() {
+        "tinyint" if column.column_type.starts_with("tinyint(1)") => Value::from(rng.gen_bool(0.5)),
+        "tinyint" => int(rng, i8::MAX.into(), u8::MAX.into()),
+        "smallint" => int(rng, i16::MAX.into(), u16::MAX.into()),
+        "mediumint" => int(rng, 8_388_607, 16_777_215),
+        "int" | "integer" => int(rng, i32::MAX.into(), u32::MAX.into()),
+        "bigint" => int(rng, i64::MAX, i64::MAX),
+        "decimal" | "numeric" => {
+            let scale = column.scale.unwrap_or(0).min(30) as usize;
+            let whole_digits = column.precision.unwrap_or(10).saturating_sub(scale as u64).min(9) as u32;
+            let whole = rng.gen_range(0..10u64.pow(whole_digits));
+            if scale == 0 {
+                Value::from(whole.to_string())
+            } else {
+                let fraction: String = (0..scale).map(|_| char::from(b'0' + rng.gen_range(0..10))).collect();
+                Value::from(format!("{whole}.{fraction}"))
+            }
+        }
+        "float" | "double" | "real" => Value::from((rng.gen_range(0.0..10_000.0f64) * 100.0).round() / 100.0),
+        "bit" => {
+            let bits = column.precision.unwrap_or(1).clamp(1, 64);
+            Value::from(rng.gen::<u64>() >> (64 - bits))
+        }
+        "date" => Value::from(random_date(rng)),
+        "datetime" | "timestamp" => Value::from(format!("{} {}", random_date(rng), random_time(rng))),
+        "time" => Value::from(random_time(rng)),
+        "year" => Value::from(rng.gen_range(1990..=2030)),
+        "enum" => enum_values(&column.column_type)
+            .choose(rng)
+            .map(|v| Value::from(v.as_str()))
+            .unwrap_or(Value::NULL),
+        "set" => {
+            let values = enum_values(&column.column_type);
+            let count = rng.gen_range(0..=values.len());
+            let chosen: Vec<&str> = values.choose_multiple(rng, count).map(String::as_str).collect();
+            Value::from(chosen.join(","))
+        }
+        "json" => Value::from(json!({ "id": rng.gen_range(1..10_000), "tag": WORDS.choose(rng) }).to_string()),
+        "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => {
+            let len = column.max_length.unwrap_or(16).min(16) as usize;
+            Value::Bytes((0..len).map(|_| rng.gen()).collect())
+        }
+        "char" | "varchar" | "tinytext" | "text" | "mediumtext" | "longtext" => Value::from(random_text(rng, column)),
+        _ => Value::NULL,
+    }
+}
+
+fn random_date(rng: &mut StdRng) -> String {
+    format!("{}-{:02}-{:02}", rng.gen_range(2000..=2030), rng.gen_range(1..=12), rng.gen_range(1..=28))
+}
+
+fn random_time(rng: &mut StdRng) -> String {
+    format!("{:02}:{:02}:{:02}", rng.gen_range(0..24), rng.gen_range(0..60), rng.gen_range(0..60))
+}
+
+/// Returns text that looks like what the column's name suggests, cut to the column's length
+fn random_text(rng: &mut StdRng, column: &Column) -> String {
+    let name = column.name.to_lowercase();
+    let first = FIRST_NAMES.choose(rng).unwrap();
+    let last = LAST_NAMES.choose(rng).unwrap();
+
+    let text = if name.contains("email") {
+        format!("{}.{}{}@example.com", first.to_lowercase(), last.to_lowercase(), rng.gen_range(1..100_000))
+    } else if name.contains("name") {
+        format!("{first} {last}")
+    } else if name.contains("phone") {
+        format!("555-{:04}", rng.gen_range(0..10_000))
+    } else if name.contains("url") {
+        format!("https://example.com/{}/{}", WORDS.choose(rng).unwrap(), rng.gen_range(1..100_000))
+    } else {
+        let count = rng.gen_range(1..=6);
+        (0..count).map(|_| *WORDS.choose(rng).unwrap()).collect::<Vec<_>>().join(" ")
+    };
+
+    match column.max_length {
+        Some(max) => text.chars().take(max as usize).collect(),
+        None => text,
+    }
+}
+
+/// What one operation did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Inserted,
+    Updated,
+    Deleted,
+    Skipped,
+}
+
+struct Generator {
+    conn: PooledConn,
+    rng: StdRng,
+    db_name: String,
+    schema: Vec<Table>,
+}
+
+impl Generator {
+    fn table(&self, name: &str) -> Option<Table> {
+        self.schema.iter().find(|t| t.name == name).cloned()
+    }
+
+    /// Returns the columns of a random row, or None if the table is empty. `RAND` is seeded from
+    /// the generator, so a seeded run picks the same rows
+    fn random_row(&mut self, table: &str, columns: &[String]) -> Result<Option<Vec<Value>>> {
+        let sql = format!(
+            "SELECT {} FROM `{}`.`{}` ORDER BY RAND({}) LIMIT 1",
+            column_list(columns),
+            self.db_name,
+            table,
+            self.rng.gen::<u32>()
+        );
+        let row: Option<Row> = self.conn.query_first(sql)?;
+        Ok(row.map(Row::unwrap))
+    }
+
+    /// Runs the write, trying again with new values while it collides with a unique key. Other
+    /// errors from MySQL, such as a foreign key violation, skip the write
+    fn write<F>(&mut self, mut statement: F) -> Result<bool>
+    where
+        F: FnMut(&mut StdRng) -> (String, Vec<Value>),
+    {
+        for _ in 0..UNIQUE_RETRIES {
+            let (sql, values) = statement(&mut self.rng);
+            match self.conn.exec_drop(&sql, values) {
+                Ok(()) => return Ok(true),
+                Err(mysql::Error::MySqlError(e)) if e.code == ER_DUP_ENTRY => continue,
+                Err(mysql::Error::MySqlError(e)) => {
+                    debug!("Skipped: {sql}: {}", e.message);
+                    return Ok(false);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Inserts a row. Foreign key columns are taken from a random parent row, inserting one first
+    /// if the parent table is empty
+    fn insert(&mut self, table: &Table, depth: usize) -> Result<bool> {
+        let mut references: BTreeMap<String, Value> = BTreeMap::new();
+
+        for fk in &table.foreign_keys {
+            let mut parent = self.random_row(&fk.referenced_table, &fk.referenced_columns)?;
+            if parent.is_none() && depth < MAX_PARENT_DEPTH && fk.referenced_table != table.name {
+                if let Some(parent_table) = self.table(&fk.referenced_table) {
+                    if self.insert(&parent_table, depth + 1)? {
+                        parent = self.random_row(&fk.referenced_table, &fk.referenced_columns)?;
+                    }
+                }
+            }
+
+            match parent {
+                Some(values) => references.extend(fk.columns.iter().cloned().zip(values)),
+                None if fk.columns.iter().all(|c| table.columns.iter().any(|col| col.name == *c && col.nullable)) => {
+                    references.extend(fk.columns.iter().map(|c| (c.clone(), Value::NULL)))
+                }
+                None => return Ok(false),
+            }
+        }
+
+        let columns: Vec<&Column> = table.columns.iter().filter(|c| !c.generated).collect();
+        let names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+        let sql = format!(
+            "INSERT INTO `{}`.`{}` ({}) VALUES ({})",
+            self.db_name,
+            table.name,
+            column_list(&names),
+            vec!["?"; names.len()].join(", ")
+        );
+
+        self.write(|rng| {
+            let values = columns
+                .iter()
+                .map(|c| match references.get(&c.name) {
+                    Some(value) => value.clone(),
+                    None if c.nullable && !table.is_key(&c.name) && rng.gen_bool(NULL_RATIO) => Value::NULL,
+                    None => random_value(rng, c),
+                })
+                .collect();
+            (sql.clone(), values)
+        })
+    }
+
+    /// Sets one or two columns of a random row to new values. Key columns are left alone
+    fn update(&mut self, table: &Table) -> Result<Outcome> {
+        let columns: Vec<Column> = table
+            .columns
+            .iter()
+            .filter(|c| !c.generated && !table.primary_key.contains(&c.name) && !table.is_foreign_key(&c.name))
+            .cloned()
+            .collect();
+        if columns.is_empty() {
+            return Ok(Outcome::Skipped);
+        }
+        let Some(key) = self.random_row(&table.name, &table.primary_key)? else {
+            return self.insert_outcome(table);
+        };
+
+        let count = self.rng.gen_range(1..=columns.len().min(2));
+        let chosen: Vec<Column> = columns.choose_multiple(&mut self.rng, count).cloned().collect();
+        let sql = format!(
+            "UPDATE `{}`.`{}` SET {} WHERE {}",
+            self.db_name,
+            table.name,
+            chosen.iter().map(|c| format!("`{}` = ?", c.name)).collect::<Vec<_>>().join(", "),
+            key_condition(&table.primary_key)
+        );
+
+        let updated = self.write(|rng| {
+            let mut values: Vec<Value> = chosen.iter().map(|c| random_value(rng, c)).collect();
+            values.extend(key.iter().cloned());
+            (sql.clone(), values)
+        })?;
+
+        Ok(if updated { Outcome::Updated } else { Outcome::Skipped })
+    }
+
+    fn delete(&mut self, table: &Table) -> Result<Outcome> {
+        let Some(key) = self.random_row(&table.name, &table.primary_key)? else {
+            return self.insert_outcome(table);
+        };
+
+        let sql = format!(
+            "DELETE FROM `{}`.`{}` WHERE {}",
+            self.db_name,
+            table.name,
+            key_condition(&table.primary_key)
+        );
+        let deleted = self.write(|_| (sql.clone(), key.clone()))?;
+
+        Ok(if deleted { Outcome::Deleted } else { Outcome::Skipped })
+    }
+
+    fn insert_outcome(&mut self, table: &Table) -> Result<Outcome> {
+        Ok(if self.insert(table, 0)? { Outcome::Inserted } else { Outcome::Skipped })
+    }
+
+    /// Runs one operation on a random table. Updates and deletes need a primary key to pick a row
+    /// by, so tables without one, and empty tables, get an insert instead
+    fn operation(&mut self, tables: &[Table], request: &GenerateRequest) -> Result<Outcome> {
+        let table = tables.choose(&mut self.rng).unwrap().clone();
+        let roll: f64 = self.rng.gen();
+
+        if table.primary_key.is_empty() {
+            self.insert_outcome(&table)
+        } else if roll < request.delete_ratio {
+            self.delete(&table)
+        } else if roll < request.delete_ratio + request.update_ratio {
+            self.update(&table)
+        } else {
+            self.insert_outcome(&table)
+        }
+    }
+}
+
+fn column_list(columns: &[String]) -> String {
+    columns.iter().map(|c| format!("`{}`", c)).collect::<Vec<_>>().join(", ")
+}
+
+fn key_condition(key: &[String]) -> String {
+    key.iter().map(|c| format!("`{}` = ?", c)).collect::<Vec<_>>().join(" AND ")
+}
+
+/// Writes random rows to the database's tables, or the requested ones, at the requested rate
+pub fn generate(server: &MySqlServer, db_name: &str, request: &GenerateRequest) -> Result<GenerateSummary> {
+    let pool = Pool::new(get_mysql_url(server, db_name).as_str())?;
+    let mut conn = pool.get_conn()?;
+    let schema = read_schema(&mut conn, db_name)?;
+
+    let tables: Vec<Table> = if request.tables.is_empty() {
+        schema.clone()
+    } else {
+        request
+            .tables
+            .iter()
+            .map(|name| {
+                schema
+                    .iter()
+                    .find(|t| t.name == *name)
+                    .cloned()
+                    .ok_or_else(|| TestBedError::UnknownTable(name.clone()))
+            })
+            .collect::<Result<_, _>>()?
+    };
+    if tables.is_empty() {
+        return Err(anyhow!("no tables to write to in: {db_name}"));
+    }
+
+    let mut generator = Generator {
+        conn,
+        rng: match request.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        },
+        db_name: db_name.to_string(),
+        schema,
+    };
+
+    let start = Instant::now();
+    let mut summary = GenerateSummary::default();
+
+    for i in 0..request.operations {
+        if let Some(rate) = request.rate {
+            let due = start + Duration::from_secs_f64(f64::from(i) / rate);
+            if let Some(wait) = due.checked_duration_since(Instant::now()) {
+                thread::sleep(wait);
+            }
+        }
+
+        match generator.operation(&tables, request)? {
+            Outcome::Inserted => summary.inserted += 1,
+            Outcome::Updated => summary.updated += 1,
+            Outcome::Deleted => summary.deleted += 1,
+            Outcome::Skipped => summary.skipped += 1,
+        }
+    }
+
+    summary.elapsed_ms = start.elapsed().as_millis() as u64;
+    info!("Generated rows in: {db_name}: {summary:?}");
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+fn column(name: &str, data_type: &str, column_type: &str) -> Column {
+    Column {
+        name: name.to_string(),
+        data_type: data_type.to_string(),
+        column_type: column_type.to_string(),
+        nullable: false,
+        max_length: None,
+        precision: None,
+        scale: None,
+        generated: false,
+    }
+}
+
+#[test]
+fn test_build_tables() {
+    let s = |s: &str| s.to_string();
+    let columns: Vec<ColumnRow> = vec![
+        (s("customers"), s("id"), s("int"), s("int"), s("NO"), None, Some(10), Some(0), s("auto_increment")),
+        (s("customers"), s("email"), s("varchar"), s("varchar(255)"), s("NO"), Some(255), None, None, s("")),
+        (s("debezium_signal"), s("id"), s("varchar"), s("varchar(42)"), s("NO"), Some(42), None, None, s("")),
+        (s("purchase_orders"), s("id"), s("int"), s("int"), s("NO"), None, Some(10), Some(0), s("auto_increment")),
+        (s("purchase_orders"), s("customer_id"), s("int"), s("int"), s("NO"), None, Some(10), Some(0), s("")),
+        (s("purchase_orders"), s("created_at"), s("datetime"), s("datetime"), s("NO"), None, None, None, s("DEFAULT_GENERATED")),
+        (s("purchase_orders"), s("total"), s("int"), s("int"), s("YES"), None, Some(10), Some(0), s("STORED GENERATED")),
+    ];
+    let keys: Vec<KeyRow> = vec![
+        (s("customers"), s("PRIMARY"), s("id"), None, None, None),
+        (s("customers"), s("email"), s("email"), None, None, None),
+        (s("purchase_orders"), s("PRIMARY"), s("id"), None, None, None),
+        (s("purchase_orders"), s("purchase_orders_ibfk_1"), s("customer_id"), Some(s("orders")), Some(s("customers")), Some(s("id"))),
+        (s("purchase_orders"), s("purchase_orders_ibfk_2"), s("id"), Some(s("other")), Some(s("audit")), Some(s("id"))),
+    ];
+
+    let tables = build_tables("orders", columns, keys);
+    assert_eq!(tables.len(), 2);
+
+    let customers = &tables[0];
+    assert_eq!(customers.primary_key, vec!["id"]);
+    assert_eq!(customers.unique_keys, vec![vec!["email"]]);
+    assert!(customers.columns[0].generated);
+    assert_eq!(customers.columns[1].max_length, Some(255));
+
+    let orders = &tables[1];
+    assert!(!orders.columns[2].generated);
+    assert!(orders.columns[3].generated);
+    assert_eq!(
+        orders.foreign_keys,
+        vec![ForeignKey {
+            columns: vec![s("customer_id")],
+            referenced_table: s("customers"),
+            referenced_columns: vec![s("id")],
+        }]
+    );
+}
+
+#[test]
+fn test_random_value() {
+    let mut rng = StdRng::seed_from_u64(7);
+
+    let status = column("status", "enum", "enum('new','it''s','paid')");
+    assert_eq!(enum_values(&status.column_type), vec!["new", "it's", "paid"]);
+
+    let code = Column {
+        max_length: Some(3),
+        ..column("code", "char", "char(3)")
+    };
+    let total = Column {
+        precision: Some(5),
+        scale: Some(2),
+        ..column("total", "decimal", "decimal(5,2)")
+    };
+
+    for _ in 0..100 {
+        let Value::Bytes(status_value) = random_value(&mut rng, &status) else {
+            panic!("enum values are strings");
+        };
+        assert!(["new", "it's", "paid"].contains(&String::from_utf8(status_value).unwrap().as_str()));
+
+        let Value::Bytes(code_value) = random_value(&mut rng, &code) else {
+            panic!("char values are strings");
+        };
+        assert!(!code_value.is_empty() && code_value.len() <= 3);
+
+        let Value::Bytes(total_value) = random_value(&mut rng, &total) else {
+            panic!("decimal values are strings");
+        };
+        let total_value = String::from_utf8(total_value).unwrap();
+        let (whole, fraction) = total_value.split_once('.').unwrap();
+        assert!(whole.len() <= 3 && fraction.len() == 2, "{total_value}");
+
+        match random_value(&mut rng, &column("id", "tinyint", "tinyint unsigned")) {
+            Value::Int(i) => assert!((0..=255).contains(&i)),
+            other => panic!("unexpected tinyint: {other:?}"),
+        }
+    }
+
+    let email = Column {
+        max_length: Some(255),
+        ..column("email", "varchar", "varchar(255)")
+    };
+    let Value::Bytes(email_value) = random_value(&mut rng, &email) else {
+        panic!("varchar values are strings");
+    };
+    assert!(String::from_utf8(email_value).unwrap().ends_with("@example.com"));
+    assert_eq!(random_value(&mut rng, &column("shape", "geometry", "geometry")), Value::NULL);
+}
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, ResourceSpecifier, TopicReplication};
+use rdkafka::client::DefaultClientContext;
+use rdkafka::types::RDKafkaErrorCode;
+use rdkafka::ClientConfig;
+use tracing::info;
+
+pub use debezium_testbed_client::types::{TopicInfo, TopicSettings};
+
+/// The stack's Kafka broker, as published on the host
+pub const KAFKA_BROKER_URL: &str = "localhost:29092";
+
+const METADATA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Lists, creates and deletes topics in the stack's Kafka broker
+#[derive(Clone)]
+pub struct KafkaAdmin {
+    admin: Arc<AdminClient<DefaultClientContext>>,
+}
+
+impl KafkaAdmin {
+    /// Creates an admin client for the brokers, i.e. `localhost:29092`
+    pub fn new(bootstrap_servers: &str) -> Result<Self> {
+        let admin = ClientConfig::new()
+            .set("bootstrap.servers", bootstrap_servers)
+            .create::<AdminClient<DefaultClientContext>>()?;
+
+        Ok(KafkaAdmin { admin: Arc::new(admin) })
+    }
+
+    /// Creates an admin client for the stack's broker
+    pub fn for_stack() -> Result<Self> {
+        KafkaAdmin::new(KAFKA_BROKER_URL)
+    }
+
+    /// Returns the names of every topic, sorted
+    pub async fn topic_names(&self) -> Result<Vec<String>> {
+        let admin = self.admin.clone();
+        let metadata = tokio::task::spawn_blocking(move || admin.inner().fetch_metadata(None, METADATA_TIMEOUT)).await??;
+
+        let mut names: Vec<String> = metadata.topics().iter().map(|t| t.name().to_string()).collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Returns the topics with the names, in the same order
+    pub async fn describe_topics(&self, names: &[String]) -> Result<Vec<TopicInfo>> {
+        if names.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let admin = self.admin.clone();
+        let metadata = tokio::task::spawn_blocking(move || admin.inner().fetch_metadata(None, METADATA_TIMEOUT)).await??;
+
+        let specifiers: Vec<ResourceSpecifier> = names.iter().map(|n| ResourceSpecifier::Topic(n)).collect();
+        let configs = self.admin.describe_configs(&specifiers, &AdminOptions::new()).await?;
+
+        let mut topics = vec![];
+        for (name, config) in names.iter().zip(configs) {
+            let partitions = metadata
+                .topics()
+                .iter()
+                .find(|t| t.name() == name)
+                .map(|t| t.partitions().len())
+                .ok_or_else(|| anyhow!("no such topic: {name}"))?;
+            let config = config.map_err(|e| anyhow!("could not describe topic {name}: {e}"))?;
+
+            topics.push(TopicInfo {
+                name: name.clone(),
+                partitions,
+                config: config
+                    .entries
+                    .into_iter()
+                    .filter(|e| !e.is_default)
+                    .filter_map(|e| e.value.map(|v| (e.name, v)))
+                    .collect(),
+            });
+        }
+
+        Ok(topics)
+    }
+
+    /// Creates the topics with the settings. Topics that already exist are left as they are
+    pub async fn create_topics(&self, names: &[String], settings: &TopicSettings) -> Result<()> {
+        let retention_ms = settings.retention_ms.map(|r| r.to_string());
+        let cleanup_policy = if settings.compact { "compact" } else { "delete" };
+
+        let topics: Vec<NewTopic> = names
+            .iter()
+            .map(|name| {
+                let topic = NewTopic::new(name, settings.partitions.unwrap_or(1), TopicReplication::Fixed(1))
+                    .set("cleanup.policy", cleanup_policy);
+                match &retention_ms {
+                    Some(retention_ms) => topic.set("retention.ms", retention_ms),
+                    None => topic,
+                }
+            })
+            .collect();
+
+        for result in self.admin.create_topics(&topics, &AdminOptions::new()).await? {
+            match result {
+                Ok(name) => info!("Created topic: {name}"),
+                Err((_, RDKafkaErrorCode::TopicAlreadyExists)) => {}
+                Err((name, e)) => return Err(anyhow!("could not create topic {name}: {e}")),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the topics. Topics that don't exist are skipped
+    pub async fn delete_topics(&self, names: &[String]) -> Result<()> {
+        if names.is_empty() {
+            return Ok(());
+        }
+
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        for result in self.admin.delete_topics(&names, &AdminOptions::new()).await? {
+            match result {
+                Ok(name) => info!("Deleted topic: {name}"),
+                Err((_, RDKafkaErrorCode::UnknownTopicOrPartition)) => {}
+                Err((name, e)) => return Err(anyhow!("could not delete topic {name}: {e}")),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the names of the testbed's topics: those prefixed with the testbed name, and its
+    /// schema history topic
+    pub async fn testbed_topics(&self, testbed_name: &str) -> Result<Vec<String>> {
+        let names = self.topic_names().await?;
+        Ok(names.into_iter().filter(|t| is_testbed_topic(t, testbed_name)).collect())
+    }
+}
+
+fn is_testbed_topic(topic: &str, testbed_name: &str) -> bool {
+    topic == testbed_name
+        || topic.starts_with(&format!("{testbed_name}."))
+        || topic == format!("schema-changes.{testbed_name}.history")
+}
+
+#[test]
+fn test_is_testbed_topic() {
+    assert!(is_testbed_topic("demo_1", "demo_1"));
+    assert!(is_testbed_topic("demo_1.demo_1.example", "demo_1"));
+    assert!(is_testbed_topic("demo_1.history", "demo_1"));
+    assert!(is_testbed_topic("schema-changes.demo_1.history", "demo_1"));
+    assert!(!is_testbed_topic("demo_12.demo_12.example", "demo_1"));
+    assert!(!is_testbed_topic("_kafka-connect-offsets", "demo_1"));
+}
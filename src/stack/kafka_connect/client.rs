@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use reqwest::header::ACCEPT;
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::info;
+
+use crate::stack::connector::Connector;
+use crate::stack::containers::kafka_connect::DebeziumVersion;
+use crate::stack::kafka_connect::types::{
+    ConfigValidation, ConnectorInfo, ConnectorOffsets, ConnectorPlugin, ConnectorStatus,
+    ConnectorTopics, ErrorMessage, TaskInfo, TaskState,
+};
+use crate::stack::kafka_connect::KAFKA_CONNECT_URL;
+
+/// An error from the Kafka Connect REST API
+#[derive(Debug)]
+pub enum KafkaConnectError {
+    /// The request couldn't be sent or the response couldn't be read
+    Http(reqwest::Error),
+    /// Kafka Connect responded with an error status
+    Api { status: StatusCode, message: String },
+    /// The connector wasn't running within the timeout; holds the last status seen, if any
+    NotRunning { name: String, status: Option<Box<ConnectorStatus>> },
+}
+
+impl KafkaConnectError {
+    /// True when Kafka Connect has no such connector, task or plugin
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, KafkaConnectError::Api { status, .. } if *status == StatusCode::NOT_FOUND)
+    }
+}
+
+impl fmt::Display for KafkaConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KafkaConnectError::Http(e) => write!(f, "kafka connect request failed: {e}"),
+            KafkaConnectError::Api { status, message } => {
+                write!(f, "kafka connect responded with {status}: {message}")
+            }
+            KafkaConnectError::NotRunning { name, status } => match status {
+                Some(status) => write!(f, "connector {name} not running, state: {}", status.connector.state),
+                None => write!(f, "connector {name} not running, no status"),
+            },
+        }
+    }
+}
+
+impl std::error::Error for KafkaConnectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KafkaConnectError::Http(e) => Some(e),
+            KafkaConnectError::Api { .. } | KafkaConnectError::NotRunning { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for KafkaConnectError {
+    fn from(e: reqwest::Error) -> Self {
+        KafkaConnectError::Http(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, KafkaConnectError>;
+
+/// A client for the Kafka Connect REST API
+#[derive(Debug, Clone)]
+pub struct KafkaConnectClient {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl Default for KafkaConnectClient {
+    /// A client for the stack's Kafka Connect worker running the default Debezium release
+    fn default() -> Self {
+        KafkaConnectClient::new(KAFKA_CONNECT_URL)
+    }
+}
+
+impl KafkaConnectClient {
+    /// Creates a client for the worker at the URL, i.e. `http://localhost:28083`
+    pub fn new(url: &str) -> Self {
+        KafkaConnectClient {
+            url: url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Creates a client for the stack's worker running the Debezium release
+    pub fn for_debezium(debezium: &DebeziumVersion) -> Self {
+        KafkaConnectClient::new(&debezium.url())
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the names of every connector
+    pub async fn connectors(&self) -> Result<Vec<String>> {
+        self.send_json(self.request(Method::GET, "/connectors")).await
+    }
+
+    /// Creates the connector
+    pub async fn create_connector(&self, connector: &Connector) -> Result<ConnectorInfo> {
+        let info = self
+            .send_json(self.request(Method::POST, "/connectors").json(connector))
+            .await?;
+        info!("Created connector: {}", connector.name);
+
+        Ok(info)
+    }
+
+    pub async fn connector(&self, name: &str) -> Result<ConnectorInfo> {
+        self.send_json(self.request(Method::GET, &format!("/connectors/{name}"))).await
+    }
+
+    pub async fn connector_config(&self, name: &str) -> Result<BTreeMap<String, String>> {
+        self.send_json(self.request(Method::GET, &format!("/connectors/{name}/config"))).await
+    }
+
+    /// Creates the connector, or updates its config if it exists
+    pub async fn put_connector_config<C: Serialize + ?Sized>(&self, name: &str, config: &C) -> Result<ConnectorInfo> {
+        self.send_json(self.request(Method::PUT, &format!("/connectors/{name}/config")).json(config))
+            .await
+    }
+
+    /// Returns the state of the connector and each of its tasks
+    pub async fn connector_status(&self, name: &str) -> Result<ConnectorStatus> {
+        self.send_json(self.request(Method::GET, &format!("/connectors/{name}/status"))).await
+    }
+
+    /// Restarts the connector; with `include_tasks` its tasks are restarted too, and with `only_failed`
+    /// only the instances that have failed are restarted
+    pub async fn restart_connector(&self, name: &str, include_tasks: bool, only_failed: bool) -> Result<()> {
+        let request = self
+            .request(Method::POST, &format!("/connectors/{name}/restart"))
+            .query(&[("includeTasks", include_tasks), ("onlyFailed", only_failed)]);
+
+        self.send(request).await?;
+        info!("Restarted connector: {name} include tasks: {include_tasks} only failed: {only_failed}");
+
+        Ok(())
+    }
+
+    /// Pauses the connector and its tasks
+    pub async fn pause_connector(&self, name: &str) -> Result<()> {
+        self.send(self.request(Method::PUT, &format!("/connectors/{name}/pause"))).await?;
+        info!("Paused connector: {name}");
+
+        Ok(())
+    }
+
+    /// Resumes a paused or stopped connector and its tasks
+    pub async fn resume_connector(&self, name: &str) -> Result<()> {
+        self.send(self.request(Method::PUT, &format!("/connectors/{name}/resume"))).await?;
+        info!("Resumed connector: {name}");
+
+        Ok(())
+    }
+
+    /// Stops the connector and shuts down its tasks; offsets can only be changed while stopped
+    pub async fn stop_connector(&self, name: &str) -> Result<()> {
+        self.send(self.request(Method::PUT, &format!("/connectors/{name}/stop"))).await?;
+        info!("Stopped connector: {name}");
+
+        Ok(())
+    }
+
+    pub async fn delete_connector(&self, name: &str) -> Result<()> {
+        self.send(self.request(Method::DELETE, &format!("/connectors/{name}"))).await?;
+        info!("Deleted connector: {name}");
+
+        Ok(())
+    }
+
+    pub async fn tasks(&self, name: &str) -> Result<Vec<TaskInfo>> {
+        self.send_json(self.request(Method::GET, &format!("/connectors/{name}/tasks"))).await
+    }
+
+    pub async fn task_status(&self, name: &str, task: u32) -> Result<TaskState> {
+        self.send_json(self.request(Method::GET, &format!("/connectors/{name}/tasks/{task}/status")))
+            .await
+    }
+
+    pub async fn restart_task(&self, name: &str, task: u32) -> Result<()> {
+        self.send(self.request(Method::POST, &format!("/connectors/{name}/tasks/{task}/restart")))
+            .await?;
+        info!("Restarted task: {task} of connector: {name}");
+
+        Ok(())
+    }
+
+    /// Returns the topics the connector has written to since it was created or its topics were reset
+    pub async fn connector_topics(&self, name: &str) -> Result<ConnectorTopics> {
+        let mut topics: BTreeMap<String, ConnectorTopics> = self
+            .send_json(self.request(Method::GET, &format!("/connectors/{name}/topics")))
+            .await?;
+
+        Ok(topics.remove(name).unwrap_or(ConnectorTopics { topics: vec![] }))
+    }
+
+    pub async fn reset_connector_topics(&self, name: &str) -> Result<()> {
+        self.send(self.request(Method::PUT, &format!("/connectors/{name}/topics/reset"))).await?;
+        Ok(())
+    }
+
+    /// Returns the connector plugins installed in the worker
+    pub async fn connector_plugins(&self) -> Result<Vec<ConnectorPlugin>> {
+        self.send_json(self.request(Method::GET, "/connector-plugins")).await
+    }
+
+    /// Validates a connector config with the plugin for its `connector.class`
+    pub async fn validate_config<C: Serialize + ?Sized>(&self, connector_class: &str, config: &C) -> Result<ConfigValidation> {
+        let path = format!("/connector-plugins/{connector_class}/config/validate");
+        self.send_json(self.request(Method::PUT, &path).json(config)).await
+    }
+
+    /// Returns the connector's committed source offsets
+    pub async fn connector_offsets(&self, name: &str) -> Result<ConnectorOffsets> {
+        self.send_json(self.request(Method::GET, &format!("/connectors/{name}/offsets"))).await
+    }
+
+    /// Overwrites the offsets of the given partitions; the connector must be stopped
+    pub async fn alter_connector_offsets(&self, name: &str, offsets: &ConnectorOffsets) -> Result<()> {
+        self.send(self.request(Method::PATCH, &format!("/connectors/{name}/offsets")).json(offsets))
+            .await?;
+        info!("Altered offsets of connector: {name}");
+
+        Ok(())
+    }
+
+    /// Removes all of the connector's offsets; the connector must be stopped
+    pub async fn reset_connector_offsets(&self, name: &str) -> Result<()> {
+        self.send(self.request(Method::DELETE, &format!("/connectors/{name}/offsets"))).await?;
+        info!("Reset offsets of connector: {name}");
+
+        Ok(())
+    }
+
+    /// Polls until the connector and all of its tasks are `RUNNING`
+    pub async fn wait_until_running(&self, name: &str, timeout: Duration) -> Result<ConnectorStatus> {
+        let start = Instant::now();
+        let mut last = None;
+
+        loop {
+            // the connector may not have been picked up by a worker yet
+            if let Ok(status) = self.connector_status(name).await {
+                if status.is_running() {
+                    info!("Connector running: {name}");
+                    return Ok(status);
+                }
+                last = Some(Box::new(status));
+            }
+
+            if start.elapsed() > timeout {
+                return Err(KafkaConnectError::NotRunning {
+                    name: name.to_string(),
+                    status: last,
+                });
+            }
+
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.url, path))
+            .header(ACCEPT, "application/json")
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response> {
+        let response = request.send().await?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let message = match serde_json::from_str::<ErrorMessage>(&body) {
+            Ok(e) => e.message,
+            Err(_) => body,
+        };
+
+        Err(KafkaConnectError::Api { status, message })
+    }
+
+    async fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        Ok(self.send(request).await?.json().await?)
+    }
+}
@@ -0,0 +1,686 @@
+//! An in-process stand-in for the part of the Kafka Connect REST API the stack uses, so connector
+//! management can be tested without Docker. Connectors go straight to the state they're asked
+//! for, and anything else, i.e. a failed task or a rejected property, is scripted by the test:
+//!
+//! ```ignore
+//! let fake = FakeKafkaConnect::start().await;
+//! fake.fail(Method::POST, "/connectors", 500, "broker unavailable");
+//! assert!(fake.client().create_connector(&connector).await.is_err());
+//! ```
+
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use reqwest::Method;
+use rocket::config::LogLevel;
+use rocket::fairing::AdHoc;
+use rocket::http::uri::Origin;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, patch, post, put, routes, Config, FromForm, Shutdown, State};
+use serde::Deserialize;
+use tokio::sync::oneshot;
+
+use crate::stack::kafka_connect::types::{
+    ConfigInfo, ConfigValidation, ConfigValueInfo, ConnectorInfo, ConnectorOffset, ConnectorOffsets,
+    ConnectorPlugin, ConnectorState, ConnectorStatus, ConnectorTopics, ErrorMessage, TaskId, TaskInfo,
+    TaskState,
+};
+use crate::stack::kafka_connect::KafkaConnectClient;
+
+const WORKER_ID: &str = "fake-connect:8083";
+
+/// The plugin every fake worker starts with
+pub const MYSQL_CONNECTOR_CLASS: &str = "io.debezium.connector.mysql.MySqlConnector";
+
+/// A connector as the fake worker holds it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeConnector {
+    pub config: BTreeMap<String, String>,
+    /// `RUNNING`, `PAUSED`, `STOPPED` or `FAILED`
+    pub state: String,
+    pub trace: Option<String>,
+    pub tasks: Vec<TaskState>,
+    pub offsets: Vec<ConnectorOffset>,
+}
+
+impl FakeConnector {
+    fn new(config: BTreeMap<String, String>, initial_state: Option<&str>) -> FakeConnector {
+        let mut connector = FakeConnector {
+            config,
+            state: String::new(),
+            trace: None,
+            tasks: vec![],
+            offsets: vec![],
+        };
+        connector.set_state(initial_state.unwrap_or("RUNNING"));
+        connector
+    }
+
+    /// Moves the connector and its tasks to the state; a stopped connector has no tasks
+    fn set_state(&mut self, state: &str) {
+        self.state = state.to_string();
+        self.trace = None;
+
+        if state == "STOPPED" {
+            self.tasks.clear();
+            return;
+        }
+
+        let tasks_max = self.config.get("tasks.max").and_then(|t| t.parse().ok()).unwrap_or(1);
+        self.tasks = (0..tasks_max)
+            .map(|id| TaskState {
+                id,
+                state: state.to_string(),
+                worker_id: WORKER_ID.to_string(),
+                trace: None,
+            })
+            .collect();
+    }
+
+    fn info(&self, name: &str) -> ConnectorInfo {
+        ConnectorInfo {
+            name: name.to_string(),
+            config: self.config.clone(),
+            tasks: self
+                .tasks
+                .iter()
+                .map(|t| TaskId {
+                    connector: name.to_string(),
+                    task: t.id,
+                })
+                .collect(),
+            connector_type: Some("source".to_string()),
+        }
+    }
+
+    fn status(&self, name: &str) -> ConnectorStatus {
+        ConnectorStatus {
+            name: name.to_string(),
+            connector: ConnectorState {
+                state: self.state.clone(),
+                worker_id: WORKER_ID.to_string(),
+                trace: self.trace.clone(),
+            },
+            tasks: self.tasks.clone(),
+            connector_type: Some("source".to_string()),
+        }
+    }
+}
+
+/// A failure scripted for the next request with the method and path
+#[derive(Debug)]
+struct Failure {
+    method: Method,
+    path: String,
+    status: Status,
+    message: String,
+}
+
+#[derive(Debug, Default)]
+struct FakeState {
+    connectors: BTreeMap<String, FakeConnector>,
+    plugins: Vec<ConnectorPlugin>,
+    /// The errors validation reports for each property
+    rejections: BTreeMap<String, Vec<String>>,
+    failures: Vec<Failure>,
+    requests: Vec<String>,
+}
+
+impl FakeState {
+    fn connector(&mut self, name: &str) -> ConnectResult<&mut FakeConnector> {
+        self.connectors
+            .get_mut(name)
+            .ok_or_else(|| error(Status::NotFound, format!("Connector {name} not found")))
+    }
+
+    /// Offsets can only be changed while the connector is stopped
+    fn stopped_connector(&mut self, name: &str) -> ConnectResult<&mut FakeConnector> {
+        let connector = self.connector(name)?;
+        if connector.state != "STOPPED" {
+            return Err(error(
+                Status::BadRequest,
+                "Connectors must be in the STOPPED state before their offsets can be modified".to_string(),
+            ));
+        }
+        Ok(connector)
+    }
+}
+
+/// The state shared by the routes and the `FakeKafkaConnect` handle
+struct Worker(Arc<Mutex<FakeState>>);
+
+impl Worker {
+    /// Records the request and fails it if a failure was scripted for it; otherwise returns the
+    /// state to serve it from
+    fn begin(&self, method: Method, uri: &Origin<'_>) -> ConnectResult<MutexGuard<'_, FakeState>> {
+        let mut state = self.0.lock().unwrap();
+        let path = uri.path().to_string();
+        state.requests.push(format!("{method} {path}"));
+
+        if let Some(i) = state.failures.iter().position(|f| f.method == method && f.path == path) {
+            let failure = state.failures.remove(i);
+            return Err(error(failure.status, failure.message));
+        }
+
+        Ok(state)
+    }
+}
+
+type ConnectResult<T> = Result<T, (Status, Json<ErrorMessage>)>;
+
+/// An error with the body Kafka Connect responds with
+fn error(status: Status, message: String) -> (Status, Json<ErrorMessage>) {
+    (
+        status,
+        Json(ErrorMessage {
+            error_code: status.code,
+            message,
+        }),
+    )
+}
+
+/// Config values are strings in Kafka Connect, whatever JSON they're sent as
+fn string_config(config: BTreeMap<String, serde_json::Value>) -> BTreeMap<String, String> {
+    config
+        .into_iter()
+        .map(|(k, v)| match v {
+            serde_json::Value::String(s) => (k, s),
+            other => (k, other.to_string()),
+        })
+        .collect()
+}
+
+/// A fake Kafka Connect worker listening on localhost, stopped when dropped
+pub struct FakeKafkaConnect {
+    url: String,
+    state: Arc<Mutex<FakeState>>,
+    shutdown: Shutdown,
+}
+
+impl FakeKafkaConnect {
+    /// Starts a worker on a free port, with the Debezium MySQL connector installed
+    pub async fn start() -> FakeKafkaConnect {
+        let state = Arc::new(Mutex::new(FakeState {
+            plugins: vec![ConnectorPlugin {
+                class: MYSQL_CONNECTOR_CLASS.to_string(),
+                plugin_type: Some("source".to_string()),
+                version: Some("2.4.2.Final".to_string()),
+            }],
+            ..Default::default()
+        }));
+
+        let mut config = Config::debug_default();
+        config.address = Ipv4Addr::LOCALHOST.into();
+        config.port = 0;
+        config.log_level = LogLevel::Off;
+        config.shutdown.ctrlc = false;
+        #[cfg(unix)]
+        config.shutdown.signals.clear();
+
+        // the port is only known once the server is listening
+        let (port_tx, port_rx) = oneshot::channel();
+        let port_tx = Mutex::new(Some(port_tx));
+
+        let rocket = rocket::custom(config)
+            .manage(Worker(state.clone()))
+            .mount("/", routes![
+                connectors,
+                create_connector,
+                connector,
+                connector_config,
+                put_connector_config,
+                connector_status,
+                restart_connector,
+                pause_connector,
+                resume_connector,
+                stop_connector,
+                delete_connector,
+                tasks,
+                task_status,
+                restart_task,
+                connector_topics,
+                reset_connector_topics,
+                connector_plugins,
+                validate_config,
+                connector_offsets,
+                alter_connector_offsets,
+                reset_connector_offsets,
+            ])
+            .attach(AdHoc::on_liftoff("Port", move |rocket| {
+                let port = rocket.config().port;
+                let port_tx = port_tx.lock().unwrap().take();
+                Box::pin(async move {
+                    if let Some(port_tx) = port_tx {
+                        let _ = port_tx.send(port);
+                    }
+                })
+            }))
+            .ignite()
+            .await
+            .expect("fake kafka connect config");
+
+        let shutdown = rocket.shutdown();
+        tokio::spawn(rocket.launch());
+        let port = port_rx.await.expect("fake kafka connect started");
+
+        FakeKafkaConnect {
+            url: format!("http://127.0.0.1:{port}"),
+            state,
+            shutdown,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns a client for this worker
+    pub fn client(&self) -> KafkaConnectClient {
+        KafkaConnectClient::new(&self.url)
+    }
+
+    /// Fails the next request with the method and path, i.e. `/connectors/tester_1/status`,
+    /// with the status and error message
+    pub fn fail(&self, method: Method, path: &str, status: u16, message: &str) {
+        self.state.lock().unwrap().failures.push(Failure {
+            method,
+            path: path.to_string(),
+            status: Status::from_code(status).unwrap_or(Status::InternalServerError),
+            message: message.to_string(),
+        });
+    }
+
+    /// Fails the connector and all of its tasks with the stack trace
+    pub fn fail_connector(&self, name: &str, trace: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(connector) = state.connectors.get_mut(name) {
+            connector.state = "FAILED".to_string();
+            connector.trace = Some(trace.to_string());
+            for task in &mut connector.tasks {
+                task.state = "FAILED".to_string();
+                task.trace = Some(trace.to_string());
+            }
+        }
+    }
+
+    /// Fails one of the connector's tasks with the stack trace, leaving the connector running
+    pub fn fail_task(&self, name: &str, task: u32, trace: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(task) = state
+            .connectors
+            .get_mut(name)
+            .and_then(|c| c.tasks.iter_mut().find(|t| t.id == task))
+        {
+            task.state = "FAILED".to_string();
+            task.trace = Some(trace.to_string());
+        }
+    }
+
+    /// Makes validation report the error for the property
+    pub fn reject(&self, property: &str, error: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .rejections
+            .entry(property.to_string())
+            .or_default()
+            .push(error.to_string());
+    }
+
+    /// Replaces the installed plugins
+    pub fn set_plugins(&self, plugins: Vec<ConnectorPlugin>) {
+        self.state.lock().unwrap().plugins = plugins;
+    }
+
+    /// Returns the connector, if it exists
+    pub fn connector(&self, name: &str) -> Option<FakeConnector> {
+        self.state.lock().unwrap().connectors.get(name).cloned()
+    }
+
+    /// Returns every request served so far, as `<method> <path>`
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for FakeKafkaConnect {
+    fn drop(&mut self) {
+        self.shutdown.clone().notify();
+    }
+}
+
+/// A connector as POSTed to `/connectors`
+#[derive(Debug, Deserialize)]
+struct NewConnector {
+    name: String,
+    config: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    initial_state: Option<String>,
+}
+
+#[derive(Debug, FromForm)]
+struct RestartQuery {
+    #[field(name = "includeTasks")]
+    include_tasks: bool,
+    #[field(name = "onlyFailed")]
+    only_failed: bool,
+}
+
+#[get("/connectors")]
+fn connectors(worker: &State<Worker>, uri: &Origin<'_>) -> ConnectResult<Json<Vec<String>>> {
+    let state = worker.begin(Method::GET, uri)?;
+    Ok(Json(state.connectors.keys().cloned().collect()))
+}
+
+#[post("/connectors", data = "<new>")]
+fn create_connector(worker: &State<Worker>, uri: &Origin<'_>, new: Json<NewConnector>) -> ConnectResult<(Status, Json<ConnectorInfo>)> {
+    let mut state = worker.begin(Method::POST, uri)?;
+    let new = new.into_inner();
+    if state.connectors.contains_key(&new.name) {
+        return Err(error(Status::Conflict, format!("Connector {} already exists", new.name)));
+    }
+
+    let mut config = string_config(new.config);
+    config.insert("name".to_string(), new.name.clone());
+    let connector = FakeConnector::new(config, new.initial_state.as_deref());
+    let info = connector.info(&new.name);
+    state.connectors.insert(new.name, connector);
+
+    Ok((Status::Created, Json(info)))
+}
+
+#[get("/connectors/<name>")]
+fn connector(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Json<ConnectorInfo>> {
+    let mut state = worker.begin(Method::GET, uri)?;
+    Ok(Json(state.connector(name)?.info(name)))
+}
+
+#[get("/connectors/<name>/config")]
+fn connector_config(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Json<BTreeMap<String, String>>> {
+    let mut state = worker.begin(Method::GET, uri)?;
+    Ok(Json(state.connector(name)?.config.clone()))
+}
+
+#[put("/connectors/<name>/config", data = "<config>")]
+fn put_connector_config(
+    worker: &State<Worker>,
+    uri: &Origin<'_>,
+    name: &str,
+    config: Json<BTreeMap<String, serde_json::Value>>,
+) -> ConnectResult<(Status, Json<ConnectorInfo>)> {
+    let mut state = worker.begin(Method::PUT, uri)?;
+    let mut config = string_config(config.into_inner());
+    config.insert("name".to_string(), name.to_string());
+
+    let status = match state.connectors.get_mut(name) {
+        Some(connector) => {
+            connector.config = config;
+            Status::Ok
+        }
+        None => {
+            state.connectors.insert(name.to_string(), FakeConnector::new(config, None));
+            Status::Created
+        }
+    };
+
+    Ok((status, Json(state.connector(name)?.info(name))))
+}
+
+#[get("/connectors/<name>/status")]
+fn connector_status(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Json<ConnectorStatus>> {
+    let mut state = worker.begin(Method::GET, uri)?;
+    Ok(Json(state.connector(name)?.status(name)))
+}
+
+#[post("/connectors/<name>/restart?<query..>")]
+fn restart_connector(worker: &State<Worker>, uri: &Origin<'_>, name: &str, query: RestartQuery) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::POST, uri)?;
+    let connector = state.connector(name)?;
+
+    if !query.only_failed || connector.state == "FAILED" {
+        connector.state = "RUNNING".to_string();
+        connector.trace = None;
+    }
+    if query.include_tasks {
+        for task in &mut connector.tasks {
+            if !query.only_failed || task.state == "FAILED" {
+                task.state = "RUNNING".to_string();
+                task.trace = None;
+            }
+        }
+    }
+
+    Ok(Status::Accepted)
+}
+
+#[put("/connectors/<name>/pause")]
+fn pause_connector(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::PUT, uri)?;
+    state.connector(name)?.set_state("PAUSED");
+    Ok(Status::Accepted)
+}
+
+#[put("/connectors/<name>/resume")]
+fn resume_connector(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::PUT, uri)?;
+    state.connector(name)?.set_state("RUNNING");
+    Ok(Status::Accepted)
+}
+
+#[put("/connectors/<name>/stop")]
+fn stop_connector(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::PUT, uri)?;
+    state.connector(name)?.set_state("STOPPED");
+    Ok(Status::Accepted)
+}
+
+#[delete("/connectors/<name>")]
+fn delete_connector(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::DELETE, uri)?;
+    state.connector(name)?;
+    state.connectors.remove(name);
+    Ok(Status::NoContent)
+}
+
+#[get("/connectors/<name>/tasks")]
+fn tasks(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Json<Vec<TaskInfo>>> {
+    let mut state = worker.begin(Method::GET, uri)?;
+    let connector = state.connector(name)?;
+    let tasks = connector
+        .tasks
+        .iter()
+        .map(|t| TaskInfo {
+            id: TaskId {
+                connector: name.to_string(),
+                task: t.id,
+            },
+            config: connector.config.clone(),
+        })
+        .collect();
+
+    Ok(Json(tasks))
+}
+
+#[get("/connectors/<name>/tasks/<task>/status")]
+fn task_status(worker: &State<Worker>, uri: &Origin<'_>, name: &str, task: u32) -> ConnectResult<Json<TaskState>> {
+    let mut state = worker.begin(Method::GET, uri)?;
+    state
+        .connector(name)?
+        .tasks
+        .iter()
+        .find(|t| t.id == task)
+        .map(|t| Json(t.clone()))
+        .ok_or_else(|| error(Status::NotFound, format!("Task {name}-{task} not found")))
+}
+
+#[post("/connectors/<name>/tasks/<task>/restart")]
+fn restart_task(worker: &State<Worker>, uri: &Origin<'_>, name: &str, task: u32) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::POST, uri)?;
+    let task_state = state
+        .connector(name)?
+        .tasks
+        .iter_mut()
+        .find(|t| t.id == task)
+        .ok_or_else(|| error(Status::NotFound, format!("Task {name}-{task} not found")))?;
+
+    task_state.state = "RUNNING".to_string();
+    task_state.trace = None;
+
+    Ok(Status::NoContent)
+}
+
+/// The fake never writes records, so connectors have no topics
+#[get("/connectors/<name>/topics")]
+fn connector_topics(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Json<BTreeMap<String, ConnectorTopics>>> {
+    let mut state = worker.begin(Method::GET, uri)?;
+    state.connector(name)?;
+    Ok(Json(BTreeMap::from([(name.to_string(), ConnectorTopics { topics: vec![] })])))
+}
+
+#[put("/connectors/<name>/topics/reset")]
+fn reset_connector_topics(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::PUT, uri)?;
+    state.connector(name)?;
+    Ok(Status::Ok)
+}
+
+#[get("/connector-plugins")]
+fn connector_plugins(worker: &State<Worker>, uri: &Origin<'_>) -> ConnectResult<Json<Vec<ConnectorPlugin>>> {
+    let state = worker.begin(Method::GET, uri)?;
+    Ok(Json(state.plugins.clone()))
+}
+
+/// Every property is valid, except those rejected by the test
+#[put("/connector-plugins/<class>/config/validate", data = "<config>")]
+fn validate_config(
+    worker: &State<Worker>,
+    uri: &Origin<'_>,
+    class: &str,
+    config: Json<BTreeMap<String, serde_json::Value>>,
+) -> ConnectResult<Json<ConfigValidation>> {
+    let state = worker.begin(Method::PUT, uri)?;
+    if !state.plugins.iter().any(|p| p.class == class) {
+        return Err(error(
+            Status::NotFound,
+            format!("Failed to find any class that implements Connector and which name matches {class}"),
+        ));
+    }
+
+    let mut values: BTreeMap<String, Option<String>> =
+        string_config(config.into_inner()).into_iter().map(|(k, v)| (k, Some(v))).collect();
+    for property in state.rejections.keys() {
+        values.entry(property.clone()).or_insert(None);
+    }
+
+    let configs: Vec<ConfigInfo> = values
+        .into_iter()
+        .map(|(name, value)| ConfigInfo {
+            definition: None,
+            value: ConfigValueInfo {
+                errors: state.rejections.get(&name).cloned().unwrap_or_default(),
+                name,
+                value,
+                recommended_values: vec![],
+                visible: true,
+            },
+        })
+        .collect();
+
+    Ok(Json(ConfigValidation {
+        name: class.to_string(),
+        error_count: configs.iter().filter(|c| !c.value.errors.is_empty()).count() as u32,
+        groups: vec!["Common".to_string()],
+        configs,
+    }))
+}
+
+#[get("/connectors/<name>/offsets")]
+fn connector_offsets(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Json<ConnectorOffsets>> {
+    let mut state = worker.begin(Method::GET, uri)?;
+    Ok(Json(ConnectorOffsets {
+        offsets: state.connector(name)?.offsets.clone(),
+    }))
+}
+
+/// Overwrites the offsets of the given partitions, removing those given a `null` offset
+#[patch("/connectors/<name>/offsets", data = "<offsets>")]
+fn alter_connector_offsets(worker: &State<Worker>, uri: &Origin<'_>, name: &str, offsets: Json<ConnectorOffsets>) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::PATCH, uri)?;
+    let connector = state.stopped_connector(name)?;
+
+    for offset in offsets.into_inner().offsets {
+        connector.offsets.retain(|o| o.partition != offset.partition);
+        if offset.offset.is_some() {
+            connector.offsets.push(offset);
+        }
+    }
+
+    Ok(Status::Ok)
+}
+
+#[delete("/connectors/<name>/offsets")]
+fn reset_connector_offsets(worker: &State<Worker>, uri: &Origin<'_>, name: &str) -> ConnectResult<Status> {
+    let mut state = worker.begin(Method::DELETE, uri)?;
+    state.stopped_connector(name)?.offsets.clear();
+    Ok(Status::Ok)
+}
+
+#[tokio::test]
+async fn test_fake_connector_lifecycle() {
+    let fake = FakeKafkaConnect::start().await;
+    let client = fake.client();
+
+    let config = serde_json::json!({"connector.class": MYSQL_CONNECTOR_CLASS, "tasks.max": 2});
+    client.put_connector_config("tester_1", &config).await.unwrap();
+    assert_eq!(client.connectors().await.unwrap(), vec!["tester_1"]);
+    assert_eq!(client.connector_config("tester_1").await.unwrap()["tasks.max"], "2");
+    assert!(client.connector_status("tester_1").await.unwrap().is_running());
+
+    client.pause_connector("tester_1").await.unwrap();
+    assert_eq!(client.connector_status("tester_1").await.unwrap().tasks[1].state, "PAUSED");
+    client.resume_connector("tester_1").await.unwrap();
+
+    fake.fail_task("tester_1", 1, "java.lang.Exception");
+    let status = client.connector_status("tester_1").await.unwrap();
+    assert!(!status.is_running());
+    assert_eq!(status.tasks[1].trace.as_deref(), Some("java.lang.Exception"));
+
+    client.restart_connector("tester_1", true, true).await.unwrap();
+    assert!(client.connector_status("tester_1").await.unwrap().is_running());
+
+    // offsets can only be changed while stopped
+    assert!(client.reset_connector_offsets("tester_1").await.is_err());
+    client.stop_connector("tester_1").await.unwrap();
+    client.reset_connector_offsets("tester_1").await.unwrap();
+
+    client.delete_connector("tester_1").await.unwrap();
+    assert!(client.connector_status("tester_1").await.unwrap_err().is_not_found());
+}
+
+#[tokio::test]
+async fn test_fake_scripted_failures() {
+    let fake = FakeKafkaConnect::start().await;
+    let client = fake.client();
+
+    fake.fail(Method::GET, "/connectors", 503, "worker is rebalancing");
+    match client.connectors().await {
+        Err(crate::stack::kafka_connect::KafkaConnectError::Api { status, message }) => {
+            assert_eq!(status.as_u16(), 503);
+            assert_eq!(message, "worker is rebalancing");
+        }
+        other => panic!("expected a scripted failure, got {other:?}"),
+    }
+    // only the next request fails
+    assert!(client.connectors().await.unwrap().is_empty());
+
+    fake.reject("database.hostname", "Unable to connect");
+    let config = serde_json::json!({"connector.class": MYSQL_CONNECTOR_CLASS, "database.user": "root"});
+    let validation = client.validate_config(MYSQL_CONNECTOR_CLASS, &config).await.unwrap();
+    assert_eq!(validation.error_count, 1);
+    assert_eq!(validation.errors()[0].property, "database.hostname");
+
+    assert!(client.validate_config("com.example.Nope", &config).await.unwrap_err().is_not_found());
+    assert_eq!(fake.requests()[0], "GET /connectors");
+}
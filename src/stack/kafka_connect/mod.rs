@@ -0,0 +1,53 @@
+use anyhow::Result;
+
+use tracing::{debug, info};
+use crate::stack::containers::mysql::MySqlServer;
+use crate::stack::containers::kafka_connect::DebeziumVersion;
+use crate::stack::templates::Template;
+use crate::stack::connector::Connector;
+
+pub mod client;
+pub mod types;
+#[cfg(any(test, feature = "testing"))]
+pub mod fake;
+
+pub use client::{KafkaConnectClient, KafkaConnectError};
+pub use types::*;
+
+
+/// The stack's Kafka Connect worker for the default Debezium release, as published on the host
+pub const KAFKA_CONNECT_URL: &str = "http://localhost:28083";
+
+/// Returns the Debezium connector for the specified database. It does this by parsing the template's
+/// `connector.json` and setting the testbed-specific properties, pointing `database.hostname`
+/// at the MySQL server the database lives on, giving it the allocated server id and using the
+/// property names of the Debezium release
+pub fn get_new_db_connector(
+    template: &Template,
+    new_db: &str,
+    server: &MySqlServer,
+    debezium: &DebeziumVersion,
+    server_id: u32,
+) -> Result<Connector> {
+    let original = get_debezium_connector_config(template)?;
+    let connector = original.render(&template.name, new_db, server, debezium, server_id);
+
+    debug!("{connector:#?}");
+
+    Ok(connector)
+}
+
+fn get_debezium_connector_config(template: &Template) -> Result<Connector> {
+    let connector = Connector::from_file(&template.connector_json())?;
+
+    info!("Original connector: {connector:?}");
+
+    Ok(connector)
+}
+
+#[test]
+fn test_parse_dbz() {
+    for template in crate::stack::templates::get_templates() {
+        get_debezium_connector_config(&template).unwrap();
+    }
+}
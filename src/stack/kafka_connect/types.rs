@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+pub use debezium_testbed_client::types::{
+    ConfigError, ConnectorOffset, ConnectorOffsets, ConnectorState, ConnectorStatus, TaskState,
+};
+
+/// A connector and its tasks, as returned by `/connectors/<name>`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectorInfo {
+    pub name: String,
+    pub config: BTreeMap<String, String>,
+    pub tasks: Vec<TaskId>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub connector_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskId {
+    pub connector: String,
+    pub task: u32,
+}
+
+/// A task and its config, as returned by `/connectors/<name>/tasks`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub config: BTreeMap<String, String>,
+}
+
+/// The topics a connector has written to, as returned by `/connectors/<name>/topics`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectorTopics {
+    pub topics: Vec<String>,
+}
+
+/// A plugin installed in the worker, as returned by `/connector-plugins`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectorPlugin {
+    pub class: String,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub plugin_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// The result of `/connector-plugins/<class>/config/validate`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigValidation {
+    pub name: String,
+    pub error_count: u32,
+    #[serde(default)]
+    pub groups: Vec<String>,
+    pub configs: Vec<ConfigInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<serde_json::Value>,
+    pub value: ConfigValueInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigValueInfo {
+    pub name: String,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub recommended_values: Vec<String>,
+    #[serde(default)]
+    pub errors: Vec<String>,
+    #[serde(default)]
+    pub visible: bool,
+}
+
+impl ConfigValidation {
+    /// Returns every property that has errors
+    pub fn errors(&self) -> Vec<ConfigError> {
+        self.configs
+            .iter()
+            .filter(|c| !c.value.errors.is_empty())
+            .map(|c| ConfigError {
+                property: c.value.name.clone(),
+                errors: c.value.errors.clone(),
+            })
+            .collect()
+    }
+}
+
+/// The body of an error response from Kafka Connect
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorMessage {
+    pub error_code: u16,
+    pub message: String,
+}
+
+#[test]
+fn test_parse_connector_status() {
+    let json = r#"{"name":"tester_1","connector":{"state":"RUNNING","worker_id":"kafka-connect:8083"},
+        "tasks":[{"id":0,"state":"FAILED","worker_id":"kafka-connect:8083","trace":"java.lang.Exception"}],
+        "type":"source"}"#;
+    let status: ConnectorStatus = serde_json::from_str(json).unwrap();
+
+    assert!(!status.is_running());
+    assert_eq!(status.tasks[0].trace.as_deref(), Some("java.lang.Exception"));
+}
+
+#[test]
+fn test_config_validation_errors() {
+    let json = r#"{"name":"io.debezium.connector.mysql.MySqlConnector","error_count":1,"groups":["Common"],
+        "configs":[
+            {"definition":{"name":"database.user"},"value":{"name":"database.user","value":"root","recommended_values":[],"errors":[],"visible":true}},
+            {"definition":{"name":"database.hostname"},"value":{"name":"database.hostname","value":"nope","recommended_values":[],"errors":["Unable to connect"],"visible":true}}
+        ]}"#;
+    let validation: ConfigValidation = serde_json::from_str(json).unwrap();
+
+    assert_eq!(
+        validation.errors(),
+        vec![ConfigError {
+            property: "database.hostname".to_string(),
+            errors: vec!["Unable to connect".to_string()],
+        }]
+    );
+}
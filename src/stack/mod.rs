@@ -1,40 +1,100 @@
+#[cfg(test)]
 use bollard::Docker;
-use bollard::container::StartContainerOptions;
 #[cfg(test)]
 use bollard::container::ListContainersOptions;
-use serde::Deserialize;
-use serde::Serialize;
 use tracing::trace;
 #[cfg(test)]
 use std::collections::HashMap;
-use bollard::exec::StartExecResults;
-use bollard::exec::CreateExecOptions;
-use futures_util::StreamExt;
+use std::collections::BTreeSet;
 
 
 pub mod kafka_connect;
 pub mod mysql;
 pub mod containers;
+pub mod error;
+pub mod templates;
+pub mod fixtures;
+pub mod connector;
+pub mod server_id;
+pub mod registry;
+pub mod converters;
+pub mod transforms;
+pub mod offsets;
+pub mod signals;
+pub mod kafka_admin;
+pub mod schema_registry;
+pub mod events;
+pub mod sql;
+pub mod generator;
+pub mod runtime;
 
 
-const DEFAULT_MYSQL_DB: &str = "mydb";
-
 use crate::stack::containers::create_testbed_network;
 use crate::stack::containers::zookeeper::{ZOOKEEPER, get_zookeeper};
 use crate::stack::containers::kafka::{KAFKA, get_kafka};
 use crate::stack::containers::schema_registry::{REGISTRY, get_registry};
-use crate::stack::containers::kafka_connect::{KAFKA_CONNECT, get_kafka_connect};
+use crate::stack::containers::kafka_connect::{DebeziumVersion, DEBEZIUM_VERSIONS, DEFAULT_DEBEZIUM, get_kafka_connect};
 use crate::stack::containers::kui::{KUI, get_kui};
-use crate::stack::containers::mysql::{MYSQL, get_mysql};
-use crate::stack::kafka_connect::create_new_connector;
+use crate::stack::containers::mysql::{MySqlServer, DEFAULT_MYSQL_SERVER, MYSQL_SERVERS, get_mysql};
 use crate::stack::kafka_connect::get_new_db_connector;
-use tracing::{error, info};
+use crate::stack::connector::Connector;
+use crate::stack::kafka_connect::{ConnectorOffsets, ConnectorStatus, KafkaConnectClient, KafkaConnectError};
+use crate::stack::offsets::{BinlogOffset, RECOVERY_SNAPSHOT_MODE};
+use crate::stack::events::{read_events, EventPage, StartPosition};
+use crate::stack::kafka_admin::{KafkaAdmin, TopicInfo};
+use crate::stack::schema_registry::{is_testbed_subject, Compatibility, Schema, SchemaRegistryClient, SubjectInfo};
+use crate::stack::sql::{execute_sql, SqlResult};
+use crate::stack::generator::{generate, GenerateRequest, GenerateSummary};
+use crate::stack::signals::{configure_signals, create_signal_table, signal_snapshot, SnapshotProgress, SnapshotSignal};
+use crate::stack::fixtures::{apply_fixtures, get_fixtures_dir, load_fixtures, Fixture, FixturePhase};
+use tracing::{debug, error, info};
+
+use crate::stack::mysql::{clone_in_server, create_db_forcefully, drop_db, get_mysql_url, list_tables, CloneStrategy};
+use crate::stack::server_id::{allocate_server_id, connector_server_ids, free_server_id};
+use crate::stack::transforms::{apply_transforms, topic_routes};
+use debezium_testbed_client::types::table_topic;
+use crate::stack::converters::{set_converters, Converter, DEFAULT_KEY_CONVERTER, DEFAULT_VALUE_CONVERTER};
+use std::time::{Duration, Instant};
+use crate::stack::error::TestBedError;
+use crate::stack::templates::{get_template, Template, DEFAULT_TEMPLATE};
+use anyhow::{anyhow, Result};
+use crate::stack::runtime::{ContainerRuntime, DockerRuntime};
+
+/// How long to wait for a new connector to start before applying `stream` fixtures
+const CONNECTOR_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long a new connector may take to snapshot the testbed database
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub use debezium_testbed_client::types::{TestBed, TestBedOptions};
+
+/// Returns the MySQL server requested by the options
+pub fn mysql_server(options: &TestBedOptions) -> Result<MySqlServer, TestBedError> {
+    match &options.server {
+        Some(version) => MySqlServer::for_version(version)
+            .ok_or_else(|| TestBedError::UnknownServer(version.clone())),
+        None => Ok(DEFAULT_MYSQL_SERVER),
+    }
+}
 
-use crate::stack::mysql::create_db_forcefully;
+/// Returns the Debezium release requested by the options, which must be able to stream from the
+/// server. Without one, the default release is used, or the first that supports the server
+pub fn debezium_version(options: &TestBedOptions, server: &MySqlServer) -> Result<DebeziumVersion, TestBedError> {
+    let debezium = match &options.debezium {
+        Some(version) => DebeziumVersion::for_version(version)
+            .ok_or_else(|| TestBedError::UnknownDebeziumVersion(version.clone()))?,
+        None if DEFAULT_DEBEZIUM.supports(server) => DEFAULT_DEBEZIUM,
+        None => DEBEZIUM_VERSIONS.into_iter().find(|d| d.supports(server)).unwrap_or(DEFAULT_DEBEZIUM),
+    };
+
+    if !debezium.supports(server) {
+        return Err(TestBedError::UnsupportedServer {
+            server: server.version.to_string(),
+            debezium: debezium.version.to_string(),
+        });
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TestBed {
-    pub name: String,
+    Ok(debezium)
 }
 
 #[derive(Debug, Clone)]
@@ -51,169 +111,903 @@ impl Stack {
     /// - Installs and configures Kafka Connect
     /// - Installs Debezium in Kafka Connect
     /// - Schema Registry
-    /// - MySQL
+    /// - MySQL, one container per server in `MYSQL_SERVERS`
     /// - Kafka UI
-    pub async fn start() {
-        let docker = Docker::connect_with_local_defaults().unwrap();
-
-        info!("Create testbed network");
-        create_testbed_network(&docker).await;
-
-        let _ = get_zookeeper(&docker).await.unwrap();
-        let _ = get_kafka(&docker).await.unwrap();
-        let _ = get_registry(&docker).await.unwrap();
-        let kc = get_kafka_connect(&docker).await.unwrap();
-        let _ = get_kui(&docker).await.unwrap();
-        let _ = get_mysql(&docker).await.unwrap();
-
-        info!("Starting zookeeper");
-        let _ = &docker
-            .start_container(ZOOKEEPER, None::<StartContainerOptions<String>>)
-            .await
-            .unwrap();
+    pub async fn start() -> Result<()> {
+        let runtime = DockerRuntime::connect()?;
+        start_stack(&runtime).await
+    }
 
-        info!("Starting kafka");
-        let _ = &docker
-            .start_container(KAFKA, None::<StartContainerOptions<String>>)
-            .await
-            .unwrap();
+    /// Stops all the images. Only failing to reach Docker is an error; containers that won't stop
+    /// are logged
+    pub async fn stop() -> Result<()> {
+        let runtime = DockerRuntime::connect()?;
+        stop_stack(&runtime).await;
+        Ok(())
+    }
 
-        info!("Starting schema-registry");
-        let _ = &docker
-            .start_container(REGISTRY, None::<StartContainerOptions<String>>)
-            .await
-            .unwrap();
+    /// Deletes all the images, logging those that can't be removed like `stop`
+    pub async fn teardown() -> Result<()> {
+        let runtime = DockerRuntime::connect()?;
+        teardown_stack(&runtime).await;
+        Ok(())
+    }
 
-        info!("Starting kafka-connect");
-        let _ = &docker
-            .start_container(KAFKA_CONNECT, None::<StartContainerOptions<String>>)
-            .await
-            .unwrap();
+    /// Stamps out a new "testbed" on the MySQL server picked in the options
+    /// and returns the configuration for that testbed. If anything fails,
+    /// whatever was created for the testbed is removed again
+    pub async fn new_testbed(test_name: &str, options: &TestBedOptions) -> Result<TestBed> {
+        let server = mysql_server(options)?;
+        let debezium = debezium_version(options, &server)?;
+        let template = get_template(options.template.as_deref().unwrap_or(DEFAULT_TEMPLATE))?;
+        let fixtures = match &options.fixtures {
+            Some(f) => Some((load_fixtures(&get_fixtures_dir(&template, &f.set)?)?, f.phase)),
+            None => None,
+        };
+        let rng = rand::random::<u32>();
+        let testbed_name = format!("{}_{}", test_name, rng);
+        let mut taken = BTreeSet::new();
+        for debezium in &DEBEZIUM_VERSIONS {
+            taken.extend(connector_server_ids(&KafkaConnectClient::for_debezium(debezium)).await);
+        }
+        let id_server = server;
+        let server_id = tokio::task::spawn_blocking(move || allocate_server_id(&id_server, taken)).await??;
+
+        let testbed = match build_testbed(&testbed_name, &server, &debezium, server_id, &template, fixtures, options).await {
+            Ok(testbed) => testbed,
+            Err(e) => {
+                error!("Could not create testbed: {testbed_name}: {e:?}");
+                // an id is only freed once nothing of the testbed is left, so a connector that
+                // couldn't be deleted never shares its id
+                match remove_testbed(&testbed_name, &server, &debezium).await {
+                    Ok(()) => free_server_id(server_id),
+                    Err(e) => error!("Could not clean up testbed: {testbed_name}: {e:?}"),
+                }
+                return Err(e);
+            }
+        };
 
-        info!("Install debezium");
-        configure_debezium(&docker, kc.id).await;
+        trace!("{testbed:#?}");
 
-        info!("Starting kafka-ui");
-        let _ = &docker
-            .start_container(KUI, None::<StartContainerOptions<String>>)
-            .await
-            .unwrap();
+        registry::register(&testbed);
+
+        Ok(testbed)
+    }
+
+    /// Deletes the testbed's connector and database, and frees its server id
+    pub async fn delete_testbed(testbed_name: &str) -> Result<()> {
+        let testbed = registry::get(testbed_name)
+            .ok_or_else(|| TestBedError::UnknownTestBed(testbed_name.to_string()))?;
+        let server = MySqlServer::for_version(&testbed.server)
+            .ok_or_else(|| TestBedError::UnknownServer(testbed.server.clone()))?;
+        let debezium = DebeziumVersion::for_version(&testbed.debezium)
+            .ok_or_else(|| TestBedError::UnknownDebeziumVersion(testbed.debezium.clone()))?;
+
+        info!("Deleting testbed: {testbed_name}");
+        // if anything fails, the testbed stays registered with its id, so deleting it again is safe
+        remove_testbed(&testbed.name, &server, &debezium).await?;
+        registry::remove(testbed_name);
+        free_server_id(testbed.server_id);
+
+        Ok(())
+    }
+
+    /// Returns the live testbed
+    pub fn testbed(testbed_name: &str) -> Result<TestBed> {
+        let testbed = registry::get(testbed_name)
+            .ok_or_else(|| TestBedError::UnknownTestBed(testbed_name.to_string()))?;
+        Ok(testbed)
+    }
+
+    /// Returns every live testbed, sorted by name
+    pub fn testbeds() -> Vec<TestBed> {
+        registry::list()
+    }
+
+    /// Runs one or more statements against the testbed database
+    pub async fn execute_sql(testbed_name: &str, sql: &str) -> Result<SqlResult> {
+        let testbed = Stack::testbed(testbed_name)?;
+        let server = MySqlServer::for_version(&testbed.server)
+            .ok_or_else(|| TestBedError::UnknownServer(testbed.server.clone()))?;
+
+        let sql = sql.to_string();
+        tokio::task::spawn_blocking(move || execute_sql(&server, &testbed.name, &sql)).await?
+    }
+
+    /// Writes random rows to the testbed's tables, following their keys and foreign keys
+    pub async fn generate(testbed_name: &str, request: &GenerateRequest) -> Result<GenerateSummary> {
+        let testbed = Stack::testbed(testbed_name)?;
+        let server = MySqlServer::for_version(&testbed.server)
+            .ok_or_else(|| TestBedError::UnknownServer(testbed.server.clone()))?;
+
+        let request = request.clone();
+        tokio::task::spawn_blocking(move || generate(&server, &testbed.name, &request)).await?
+    }
+
+    /// Returns the state of the testbed's connector and its tasks, including failure traces
+    pub async fn connector_status(testbed_name: &str) -> Result<ConnectorStatus> {
+        let kafka_connect = kafka_connect_for(testbed_name)?;
+        let status = kafka_connect.connector_status(testbed_name).await;
+        status.map_err(|e| connector_error(testbed_name, e))
+    }
+
+    /// Pauses the testbed's connector
+    pub async fn pause_connector(testbed_name: &str) -> Result<ConnectorStatus> {
+        let kafka_connect = kafka_connect_for(testbed_name)?;
+        kafka_connect.pause_connector(testbed_name).await.map_err(|e| connector_error(testbed_name, e))?;
+        Stack::connector_status(testbed_name).await
+    }
 
-        info!("Starting db");
-        let _ = &docker
-            .start_container(MYSQL, None::<StartContainerOptions<String>>)
+    /// Resumes the testbed's paused connector
+    pub async fn resume_connector(testbed_name: &str) -> Result<ConnectorStatus> {
+        let kafka_connect = kafka_connect_for(testbed_name)?;
+        kafka_connect.resume_connector(testbed_name).await.map_err(|e| connector_error(testbed_name, e))?;
+        Stack::connector_status(testbed_name).await
+    }
+
+    /// Restarts the testbed's connector and, optionally, its tasks
+    pub async fn restart_connector(testbed_name: &str, include_tasks: bool, only_failed: bool) -> Result<ConnectorStatus> {
+        let kafka_connect = kafka_connect_for(testbed_name)?;
+        kafka_connect
+            .restart_connector(testbed_name, include_tasks, only_failed)
             .await
-            .unwrap();
+            .map_err(|e| connector_error(testbed_name, e))?;
+        Stack::connector_status(testbed_name).await
     }
 
-    /// Stops all the images
-    pub async fn stop() {
-        let docker = Docker::connect_with_local_defaults().unwrap();
+    /// Returns the source offsets of the testbed's connector
+    pub async fn connector_offsets(testbed_name: &str) -> Result<ConnectorOffsets> {
+        let kafka_connect = kafka_connect_for(testbed_name)?;
+        let offsets = kafka_connect.connector_offsets(testbed_name).await;
+        offsets.map_err(|e| connector_error(testbed_name, e))
+    }
 
-        info!("Stop mysql");
-        if let Err(e) = &docker.stop_container(MYSQL, None).await {
-            error!("{e:?}");
+    /// Moves the testbed's connector to the binlog position, or with no position clears its offsets
+    /// so it snapshots again. The connector is stopped while its offsets change
+    pub async fn reset_connector_offsets(testbed_name: &str, offset: Option<&BinlogOffset>) -> Result<ConnectorOffsets> {
+        let kafka_connect = kafka_connect_for(testbed_name)?;
+        reset_offsets(&kafka_connect, testbed_name, offset).await?;
+        Stack::connector_offsets(testbed_name).await
+    }
+
+    /// Returns the testbed's topics
+    pub async fn testbed_topics(testbed_name: &str) -> Result<Vec<TopicInfo>> {
+        if registry::get(testbed_name).is_none() {
+            return Err(TestBedError::UnknownTestBed(testbed_name.to_string()).into());
         }
 
-        info!("Stop kafka-ui");
-        if let Err(e) = &docker.stop_container(KUI, None).await {
-            error!("{e:?}");
+        let admin = KafkaAdmin::for_stack()?;
+        let names = admin.testbed_topics(testbed_name).await?;
+        admin.describe_topics(&names).await
+    }
+
+    /// Returns the testbed's registry subjects, with their versions and compatibility levels
+    pub async fn testbed_subjects(testbed_name: &str) -> Result<Vec<SubjectInfo>> {
+        if registry::get(testbed_name).is_none() {
+            return Err(TestBedError::UnknownTestBed(testbed_name.to_string()).into());
         }
 
-        info!("Stop kafka-connect");
-        if let Err(e) = &docker.stop_container(KAFKA_CONNECT, None).await {
-            error!("{e:?}");
+        let schema_registry = SchemaRegistryClient::default();
+        let mut subjects = vec![];
+        for subject in schema_registry.subjects().await? {
+            if is_testbed_subject(&subject, testbed_name) {
+                subjects.push(schema_registry.subject_info(&subject).await?);
+            }
         }
 
-        info!("Stop schema-registry");
-        if let Err(e) = &docker.stop_container(REGISTRY, None).await {
-            error!("{e:?}");
+        Ok(subjects)
+    }
+
+    /// Returns a version of the schema of one of the testbed's subjects; `version` is a version
+    /// number or `latest`
+    pub async fn testbed_schema(testbed_name: &str, subject: &str, version: &str) -> Result<Schema> {
+        if registry::get(testbed_name).is_none() {
+            return Err(TestBedError::UnknownTestBed(testbed_name.to_string()).into());
+        }
+        if !is_testbed_subject(subject, testbed_name) {
+            return Err(TestBedError::UnknownSubject(subject.to_string()).into());
         }
 
-        info!("Stop kafka");
-        if let Err(e) = &docker.stop_container(KAFKA, None).await {
-            error!("{e:?}");
+        match SchemaRegistryClient::default().schema(subject, version).await {
+            Err(e) if e.is_not_found() => Err(TestBedError::UnknownSubject(format!("{subject}/{version}")).into()),
+            schema => Ok(schema?),
         }
+    }
 
-        info!("Stop zookeeper");
-        if let Err(e) = &docker.stop_container(ZOOKEEPER, None).await {
-            error!("{e:?}");
+    /// Reads a page of decoded events from the topic of one of the testbed's tables
+    pub async fn testbed_events(testbed_name: &str, table: &str, start: StartPosition, limit: usize) -> Result<EventPage> {
+        let testbed = registry::get(testbed_name)
+            .ok_or_else(|| TestBedError::UnknownTestBed(testbed_name.to_string()))?;
+
+        read_events(&testbed.topic(table), start, limit, testbed.key_converter, testbed.value_converter).await
+    }
+
+    /// Signals the testbed's connector to snapshot the tables while it keeps streaming
+    pub async fn incremental_snapshot(testbed_name: &str, tables: &[String]) -> Result<SnapshotSignal> {
+        let testbed = registry::get(testbed_name)
+            .ok_or_else(|| TestBedError::UnknownTestBed(testbed_name.to_string()))?;
+        let server = MySqlServer::for_version(&testbed.server)
+            .ok_or_else(|| TestBedError::UnknownServer(testbed.server.clone()))?;
+
+        let db_name = testbed.name.clone();
+        let tables = tables.to_vec();
+        let (id, tables) = tokio::task::spawn_blocking(move || signal_snapshot(&server, &db_name, &tables)).await??;
+
+        Ok(SnapshotSignal {
+            id,
+            tables,
+            progress: Stack::snapshot_progress(testbed_name).await?,
+        })
+    }
+
+    /// Returns the progress of the testbed's incremental snapshot
+    pub async fn snapshot_progress(testbed_name: &str) -> Result<SnapshotProgress> {
+        let offsets = Stack::connector_offsets(testbed_name).await?;
+        Ok(SnapshotProgress::from_offsets(&offsets))
+    }
+}
+
+/// Returns the name of every container in the stack, in the order they're started
+pub fn stack_containers() -> Vec<&'static str> {
+    let mut names = vec![ZOOKEEPER, KAFKA, REGISTRY];
+    names.extend(DEBEZIUM_VERSIONS.iter().map(|d| d.worker));
+    names.push(KUI);
+    names.extend(MYSQL_SERVERS.iter().map(|s| s.name));
+    names
+}
+
+/// Creates every container in the stack and starts them in order, installing Debezium in each
+/// Connect worker as it starts
+pub async fn start_stack(runtime: &dyn ContainerRuntime) -> Result<()> {
+    info!("Create testbed network");
+    create_testbed_network(runtime).await;
+
+    get_zookeeper(runtime).await?;
+    get_kafka(runtime).await?;
+    get_registry(runtime).await?;
+    for debezium in &DEBEZIUM_VERSIONS {
+        get_kafka_connect(runtime, debezium).await?;
+    }
+    get_kui(runtime).await?;
+    for server in &MYSQL_SERVERS {
+        get_mysql(runtime, server).await?;
+    }
+
+    for name in stack_containers() {
+        start_container(runtime, name).await?;
+
+        if let Some(debezium) = DEBEZIUM_VERSIONS.iter().find(|d| d.worker == name) {
+            info!("Install debezium {}", debezium.plugin_version);
+            configure_debezium(runtime, debezium).await?;
         }
     }
 
-    /// Deletes all the images
-    pub async fn teardown() {
-        let docker = Docker::connect_with_local_defaults().unwrap();
+    Ok(())
+}
 
-        info!("Remove mysql");
-        if let Err(e) = &docker.remove_container(MYSQL, None).await {
-            error!("{e:?}");
+/// Starts the container; if it won't start, its last lines of output are logged
+async fn start_container(runtime: &dyn ContainerRuntime, name: &str) -> Result<()> {
+    info!("Starting {name}");
+    if let Err(e) = runtime.start(name).await {
+        if let Ok(logs) = runtime.logs(name, 50).await {
+            error!("{name} output:\n{logs}");
         }
+        return Err(e.context(format!("could not start {name}")));
+    }
+
+    Ok(())
+}
 
-        info!("Remove kafka-ui");
-        if let Err(e) = &docker.remove_container(KUI, None).await {
+/// Stops every container in the stack, in the reverse of the order they're started. Errors are
+/// logged, so one container that won't stop doesn't keep the others running
+pub async fn stop_stack(runtime: &dyn ContainerRuntime) {
+    for name in stack_containers().into_iter().rev() {
+        info!("Stop {name}");
+        if let Err(e) = runtime.stop(name).await {
             error!("{e:?}");
         }
+    }
+}
 
-        info!("Remove kafka-connect");
-        if let Err(e) = &docker.remove_container(KAFKA_CONNECT, None).await {
+/// Removes every container in the stack and prunes unused volumes. Errors are logged, like those of
+/// `stop_stack`
+pub async fn teardown_stack(runtime: &dyn ContainerRuntime) {
+    for name in stack_containers().into_iter().rev() {
+        info!("Remove {name}");
+        if let Err(e) = runtime.remove(name).await {
             error!("{e:?}");
         }
+    }
 
-        info!("Remove schema-registry");
-        if let Err(e) = &docker.remove_container(REGISTRY, None).await {
-            error!("{e:?}");
+    info!("Prune volumes");
+    if let Err(e) = runtime.prune_volumes().await {
+        error!("{e:?}");
+    }
+}
+
+/// Creates the testbed's database and connector
+async fn build_testbed(
+    testbed_name: &str,
+    server: &MySqlServer,
+    debezium: &DebeziumVersion,
+    server_id: u32,
+    template: &Template,
+    fixtures: Option<(Vec<Fixture>, FixturePhase)>,
+    options: &TestBedOptions,
+) -> Result<TestBed> {
+    let mut connector = get_new_db_connector(template, testbed_name, server, debezium, server_id)?;
+
+    let key_converter = options.key_converter.unwrap_or(DEFAULT_KEY_CONVERTER);
+    let value_converter = options.value_converter.unwrap_or(DEFAULT_VALUE_CONVERTER);
+    if options.key_converter.is_some() || options.value_converter.is_some() {
+        set_converters(&mut connector.config, key_converter, value_converter);
+    }
+    apply_transforms(&mut connector.config, &options.transforms, testbed_name);
+    configure_signals(&mut connector.config, testbed_name);
+
+    // a seeded connector is created stopped, as offsets can only be altered on a stopped connector
+    if options.offset.is_some() {
+        connector.config.set("snapshot.mode", RECOVERY_SNAPSHOT_MODE);
+        connector.initial_state = Some("STOPPED".to_string());
+    }
+
+    trace!("{connector:?}");
+
+    // validate before cloning so a broken connector leaves nothing behind
+    let kafka_connect = KafkaConnectClient::for_debezium(debezium);
+    validate_connector(&kafka_connect, &connector).await?;
+
+    let runtime = DockerRuntime::connect()?;
+    let clone_time = clone_database(&runtime, server, &template.name, testbed_name, options.clone_strategy).await?;
+
+    let signal_server = *server;
+    let db_name = testbed_name.to_string();
+    tokio::task::spawn_blocking(move || create_signal_table(&signal_server, &db_name)).await??;
+
+    if options.topics.is_some() || options.compatibility.is_some() {
+        let topics = captured_topics(server, testbed_name, &connector).await?;
+
+        if let Some(settings) = &options.topics {
+            KafkaAdmin::for_stack()?.create_topics(&topics, settings).await?;
+        }
+        if let Some(compatibility) = options.compatibility {
+            set_compatibility(testbed_name, &topics, key_converter, value_converter, compatibility).await?;
         }
+    }
 
-        info!("Remove kafka");
-        if let Err(e) = &docker.remove_container(KAFKA, None).await {
-            error!("{e:?}");
+    if let Some((fixtures, FixturePhase::Snapshot)) = &fixtures {
+        apply_testbed_fixtures(server, testbed_name, fixtures.clone()).await?;
+    }
+
+    create_new_connector(&kafka_connect, &connector, options.offset.as_ref()).await?;
+
+    // rows written while the connector still snapshots could be read by the snapshot as `r` events
+    if let Some((fixtures, FixturePhase::Stream)) = &fixtures {
+        kafka_connect.wait_until_running(testbed_name, CONNECTOR_STARTUP_TIMEOUT).await?;
+        wait_until_snapshot_done(&kafka_connect, testbed_name, SNAPSHOT_TIMEOUT).await?;
+        apply_testbed_fixtures(server, testbed_name, fixtures.clone()).await?;
+    }
+
+    Ok(TestBed {
+        name: testbed_name.to_string(),
+        template: template.name.clone(),
+        server: server.version.to_string(),
+        debezium: debezium.version.to_string(),
+        mysql_url: get_mysql_url(server, testbed_name),
+        server_id,
+        key_converter,
+        value_converter,
+        clone_strategy: options.clone_strategy,
+        clone_ms: clone_time.as_millis() as u64,
+        topic_routes: topic_routes(&connector.config),
+    })
+}
+
+/// Fails with the properties Kafka Connect rejects, if any
+async fn validate_connector(kafka_connect: &KafkaConnectClient, connector: &Connector) -> Result<()> {
+    let validation = kafka_connect
+        .validate_config(&connector.config.connector_class, &connector.config)
+        .await?;
+    info!("Validated connector: {} with {} errors", connector.name, validation.error_count);
+
+    let errors = validation.errors();
+    if !errors.is_empty() {
+        return Err(TestBedError::InvalidConnectorConfig(errors).into());
+    }
+
+    Ok(())
+}
+
+/// Creates the connector. With an offset, the connector must be created stopped; its offsets are
+/// seeded and then it's resumed
+async fn create_new_connector(kafka_connect: &KafkaConnectClient, connector: &Connector, offset: Option<&BinlogOffset>) -> Result<()> {
+    kafka_connect.create_connector(connector).await?;
+
+    if let Some(offset) = offset {
+        kafka_connect
+            .alter_connector_offsets(&connector.name, &offset.connector_offsets(&connector.name))
+            .await?;
+        kafka_connect.resume_connector(&connector.name).await?;
+    }
+
+    Ok(())
+}
+
+/// Stops the connector, moves or clears its offsets and resumes it. If the offsets can't be changed,
+/// the connector is resumed on its old ones
+async fn reset_offsets(kafka_connect: &KafkaConnectClient, name: &str, offset: Option<&BinlogOffset>) -> Result<()> {
+    kafka_connect.stop_connector(name).await.map_err(|e| connector_error(name, e))?;
+
+    let changed = match offset {
+        Some(offset) => kafka_connect.alter_connector_offsets(name, &offset.connector_offsets(name)).await,
+        None => clear_offsets(kafka_connect, name).await,
+    };
+
+    if let Err(e) = changed {
+        if let Err(resume) = kafka_connect.resume_connector(name).await {
+            error!("Could not resume connector: {name}: {resume}");
+            return Err(connector_error(name, e).context(format!("connector {name} is left stopped")));
         }
+        return Err(connector_error(name, e));
+    }
 
-        info!("Remove zookeeper");
-        if let Err(e) = &docker.remove_container(ZOOKEEPER, None).await {
-            error!("{e:?}");
+    kafka_connect.resume_connector(name).await.map_err(|e| connector_error(name, e))
+}
+
+/// Clears the offsets of the stopped connector. Other snapshot modes, such as a seeded connector's
+/// `schema_only_recovery`, don't snapshot a connector without offsets, so it's set back to `initial`
+async fn clear_offsets(kafka_connect: &KafkaConnectClient, name: &str) -> std::result::Result<(), KafkaConnectError> {
+    kafka_connect.reset_connector_offsets(name).await?;
+
+    let mut config = kafka_connect.connector_config(name).await?;
+    if config.get("snapshot.mode").is_some_and(|mode| mode != "initial") {
+        config.insert("snapshot.mode".to_string(), "initial".to_string());
+        kafka_connect.put_connector_config(name, &config).await?;
+    }
+
+    Ok(())
+}
+
+/// Deletes the testbed's connector, registry subjects, database and topics if they exist. The
+/// caller frees the server id once this succeeds
+async fn remove_testbed(testbed_name: &str, server: &MySqlServer, debezium: &DebeziumVersion) -> Result<()> {
+    let kafka_connect = KafkaConnectClient::for_debezium(debezium);
+    match kafka_connect.delete_connector(testbed_name).await {
+        Err(e) if !e.is_not_found() => return Err(e.into()),
+        _ => {}
+    }
+
+    delete_subjects(server, testbed_name).await?;
+    let drop_server = *server;
+    let db_name = testbed_name.to_string();
+    tokio::task::spawn_blocking(move || drop_db(&drop_server, &db_name)).await??;
+
+    let admin = KafkaAdmin::for_stack()?;
+    let topics = admin.testbed_topics(testbed_name).await?;
+    admin.delete_topics(&topics).await?;
+
+    Ok(())
+}
+
+/// Returns the topics of the tables the connector captures, named `<prefix>.<db>.<table>` like
+/// Debezium's own, or as its routes rename them. Tables routed to the same topic share it
+async fn captured_topics(server: &MySqlServer, testbed_name: &str, connector: &Connector) -> Result<Vec<String>> {
+    let routes = topic_routes(&connector.config);
+    let mut topics = vec![];
+    for table in testbed_tables(server, testbed_name).await? {
+        if connector.config.captures(&format!("{testbed_name}.{table}"))? {
+            let topic = table_topic(testbed_name, &table, &routes);
+            if !topics.contains(&topic) {
+                topics.push(topic);
+            }
         }
+    }
 
-        info!("Prune volumes");
-        if let Err(e) = &docker.prune_volumes::<String>(None).await {
-            error!("{e:?}");
+    Ok(topics)
+}
+
+async fn testbed_tables(server: &MySqlServer, testbed_name: &str) -> Result<Vec<String>> {
+    let server = *server;
+    let db_name = testbed_name.to_string();
+    tokio::task::spawn_blocking(move || list_tables(&server, &db_name)).await?
+}
+
+/// Sets the compatibility level of the subjects the connector will register for the table topics
+/// and the schema change topic, for the key and value converters that use the registry
+async fn set_compatibility(
+    testbed_name: &str,
+    topics: &[String],
+    key_converter: Converter,
+    value_converter: Converter,
+    compatibility: Compatibility,
+) -> Result<()> {
+    let schema_registry = SchemaRegistryClient::default();
+
+    for topic in topics.iter().map(String::as_str).chain([testbed_name]) {
+        if key_converter.uses_registry() {
+            schema_registry.set_compatibility(&format!("{topic}-key"), compatibility).await?;
         }
+        if value_converter.uses_registry() {
+            schema_registry.set_compatibility(&format!("{topic}-value"), compatibility).await?;
+        }
+    }
 
+    Ok(())
+}
 
+/// Deletes the testbed's subjects and their compatibility levels. Levels can be set on subjects
+/// that never got a schema, which the registry doesn't list, so every subject the testbed's
+/// tables could have is cleared as well
+async fn delete_subjects(server: &MySqlServer, testbed_name: &str) -> Result<()> {
+    let schema_registry = SchemaRegistryClient::default();
+
+    let mut subjects: Vec<String> = schema_registry
+        .subjects()
+        .await?
+        .into_iter()
+        .filter(|s| is_testbed_subject(s, testbed_name))
+        .collect();
+    for topic in testbed_tables(server, testbed_name)
+        .await?
+        .iter()
+        .map(|table| format!("{testbed_name}.{testbed_name}.{table}"))
+        .chain([testbed_name.to_string()])
+    {
+        subjects.push(format!("{topic}-key"));
+        subjects.push(format!("{topic}-value"));
     }
+    subjects.sort();
+    subjects.dedup();
 
-    /// Stamps out a new "testbed" and returns the
-    /// configuration for that testbed
-    pub async fn new_testbed(test_name: &str) -> TestBed {
-        let rng = rand::random::<u32>();
-        let testbed_name = format!("{}_{}", test_name, rng);
-        
+    for subject in &subjects {
+        match schema_registry.delete_compatibility(subject).await {
+            Err(e) if !e.is_not_found() => return Err(e.into()),
+            _ => {}
+        }
+        match schema_registry.delete_subject(subject).await {
+            Err(e) if !e.is_not_found() => return Err(e.into()),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the client for the Connect worker running the testbed's connector
+fn kafka_connect_for(testbed_name: &str) -> Result<KafkaConnectClient> {
+    let testbed = registry::get(testbed_name)
+        .ok_or_else(|| TestBedError::UnknownTestBed(testbed_name.to_string()))?;
+    let debezium = DebeziumVersion::for_version(&testbed.debezium)
+        .ok_or_else(|| TestBedError::UnknownDebeziumVersion(testbed.debezium.clone()))?;
+
+    Ok(KafkaConnectClient::for_debezium(&debezium))
+}
+
+/// Connectors are named after their testbed, so a connector Kafka Connect doesn't know is an unknown testbed
+fn connector_error(testbed_name: &str, e: KafkaConnectError) -> anyhow::Error {
+    if e.is_not_found() {
+        TestBedError::UnknownTestBed(testbed_name.to_string()).into()
+    } else {
+        e.into()
+    }
+}
+
+/// Waits until the connector has committed offsets past its initial snapshot, so it's reading the binlog
+async fn wait_until_snapshot_done(kafka_connect: &KafkaConnectClient, name: &str, timeout: Duration) -> Result<()> {
+    let start = Instant::now();
+
+    loop {
+        // offsets are only committed once the first records are flushed
+        let offsets = kafka_connect.connector_offsets(name).await?;
+        if !offsets.offsets.is_empty() && !offsets.in_snapshot() {
+            info!("Connector snapshot done: {name}");
+            return Ok(());
+        }
+
+        if start.elapsed() > timeout {
+            return Err(anyhow!("connector {name} did not finish its snapshot within {}s", timeout.as_secs()));
+        }
 
-        let docker = Docker::connect_with_local_defaults().unwrap();
-        clone_database(&docker, None, &testbed_name).await;
-        let json = &get_new_db_connector(None, &testbed_name);
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
 
-        trace!("{json:?}");
-        println!("{json:?}");
+#[tokio::test]
+async fn create_testbed() {
+    Stack::new_testbed("tester", &TestBedOptions::default()).await.unwrap();
+}
 
-        create_new_connector(json).await;
+#[tokio::test]
+async fn delete_testbed() {
+    let testbed = Stack::new_testbed("tester", &TestBedOptions::default()).await.unwrap();
+    Stack::delete_testbed(&testbed.name).await.unwrap();
+    assert!(registry::get(&testbed.name).is_none());
+}
 
-        let testbed = TestBed {
-            name: testbed_name,
+#[tokio::test]
+async fn create_testbed_on_each_server() {
+    for server in &MYSQL_SERVERS {
+        let options = TestBedOptions {
+            server: Some(server.version.to_string()),
+            ..Default::default()
         };
+        let testbed = Stack::new_testbed("tester", &options).await.unwrap();
+        assert_eq!(testbed.server, server.version);
+    }
+}
 
-        trace!("{testbed:#?}");
-        println!("{testbed:#?}");
+#[test]
+fn test_unknown_server() {
+    let options = TestBedOptions {
+        server: Some("4.1".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        mysql_server(&options),
+        Err(TestBedError::UnknownServer("4.1".to_string()))
+    );
+}
+
+#[test]
+fn test_unknown_debezium_version() {
+    let options = TestBedOptions {
+        debezium: Some("0.9".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        debezium_version(&options, &DEFAULT_MYSQL_SERVER),
+        Err(TestBedError::UnknownDebeziumVersion("0.9".to_string()))
+    );
+    assert_eq!(debezium_version(&TestBedOptions::default(), &DEFAULT_MYSQL_SERVER), Ok(DEFAULT_DEBEZIUM));
+}
+
+#[test]
+fn test_debezium_version_for_mysql_84() {
+    use crate::stack::containers::kafka_connect::DEBEZIUM_27;
+    use crate::stack::containers::mysql::MYSQL_84;
+
+    assert_eq!(debezium_version(&TestBedOptions::default(), &MYSQL_84), Ok(DEBEZIUM_27));
+
+    let options = TestBedOptions {
+        debezium: Some("1.7".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        debezium_version(&options, &MYSQL_84),
+        Err(TestBedError::UnsupportedServer {
+            server: "8.4".to_string(),
+            debezium: "1.7".to_string(),
+        })
+    );
+}
+
+#[cfg(test)]
+fn test_connector(initial_state: Option<&str>) -> Connector {
+    use crate::stack::containers::kafka_connect::DEBEZIUM_24;
+    use crate::stack::containers::mysql::MYSQL_57;
+
+    let mut connector = get_new_db_connector(&get_template("mydb").unwrap(), "tester_1", &MYSQL_57, &DEBEZIUM_24, 42).unwrap();
+    connector.initial_state = initial_state.map(str::to_string);
+    connector
+}
+
+#[tokio::test]
+async fn test_validate_connector() {
+    use crate::stack::kafka_connect::fake::FakeKafkaConnect;
+
+    let fake = FakeKafkaConnect::start().await;
+    let connector = test_connector(None);
+    validate_connector(&fake.client(), &connector).await.unwrap();
+
+    fake.reject("database.hostname", "Unable to connect");
+    let e = validate_connector(&fake.client(), &connector).await.unwrap_err();
+    match e.downcast_ref::<TestBedError>() {
+        Some(TestBedError::InvalidConnectorConfig(errors)) => assert_eq!(errors[0].property, "database.hostname"),
+        other => panic!("expected an invalid connector config, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_create_new_connector() {
+    use crate::stack::kafka_connect::fake::FakeKafkaConnect;
+
+    let fake = FakeKafkaConnect::start().await;
+    create_new_connector(&fake.client(), &test_connector(None), None).await.unwrap();
+    assert_eq!(fake.connector("tester_1").unwrap().config["database.server.id"], "42");
+    assert!(fake.client().connector_status("tester_1").await.unwrap().is_running());
+
+    // a failed create surfaces as an error
+    fake.fail(reqwest::Method::POST, "/connectors", 500, "broker unavailable");
+    assert!(create_new_connector(&fake.client(), &test_connector(None), None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_create_seeded_connector() {
+    use crate::stack::kafka_connect::fake::FakeKafkaConnect;
+
+    let fake = FakeKafkaConnect::start().await;
+    let offset = BinlogOffset {
+        file: "mysql-bin.000003".to_string(),
+        pos: 154,
+        gtids: None,
+    };
+    create_new_connector(&fake.client(), &test_connector(Some("STOPPED")), Some(&offset)).await.unwrap();
+
+    let connector = fake.connector("tester_1").unwrap();
+    assert_eq!(connector.state, "RUNNING");
+    assert_eq!(connector.offsets, offset.connector_offsets("tester_1").offsets);
+    assert_eq!(
+        fake.requests(),
+        vec![
+            "POST /connectors",
+            "PATCH /connectors/tester_1/offsets",
+            "PUT /connectors/tester_1/resume",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_reset_offsets() {
+    use crate::stack::kafka_connect::fake::FakeKafkaConnect;
+    use reqwest::Method;
+
+    let fake = FakeKafkaConnect::start().await;
+    let mut connector = test_connector(None);
+    connector.config.set("snapshot.mode", RECOVERY_SNAPSHOT_MODE);
+    fake.client().create_connector(&connector).await.unwrap();
+
+    let offset = BinlogOffset {
+        file: "mysql-bin.000003".to_string(),
+        pos: 154,
+        gtids: None,
+    };
+    reset_offsets(&fake.client(), "tester_1", Some(&offset)).await.unwrap();
+    let seeked = fake.connector("tester_1").unwrap();
+    assert_eq!(seeked.state, "RUNNING");
+    assert_eq!(seeked.offsets, offset.connector_offsets("tester_1").offsets);
+
+    // cleared offsets are snapshotted again
+    reset_offsets(&fake.client(), "tester_1", None).await.unwrap();
+    let cleared = fake.connector("tester_1").unwrap();
+    assert_eq!(cleared.state, "RUNNING");
+    assert!(cleared.offsets.is_empty());
+    assert_eq!(cleared.config["snapshot.mode"], "initial");
+
+    // a failed change leaves the connector running on its old offsets
+    fake.fail(Method::PATCH, "/connectors/tester_1/offsets", 500, "offsets topic unavailable");
+    assert!(reset_offsets(&fake.client(), "tester_1", Some(&offset)).await.is_err());
+    assert_eq!(fake.connector("tester_1").unwrap().state, "RUNNING");
+    assert!(fake.connector("tester_1").unwrap().offsets.is_empty());
+}
+
+#[tokio::test]
+async fn test_wait_until_snapshot_done() {
+    use crate::stack::kafka_connect::fake::FakeKafkaConnect;
 
-        testbed
+    let fake = FakeKafkaConnect::start().await;
+    let client = fake.client();
+    client.create_connector(&test_connector(Some("STOPPED"))).await.unwrap();
+
+    let mut offsets = BinlogOffset {
+        file: "mysql-bin.000003".to_string(),
+        pos: 154,
+        gtids: None,
     }
+    .connector_offsets("tester_1");
+    offsets.offsets[0].offset.as_mut().unwrap()["snapshot"] = serde_json::Value::Bool(true);
+    client.alter_connector_offsets("tester_1", &offsets).await.unwrap();
+    assert!(wait_until_snapshot_done(&client, "tester_1", Duration::from_millis(600)).await.is_err());
+
+    offsets.offsets[0].offset.as_mut().unwrap()["snapshot"] = serde_json::Value::Null;
+    client.alter_connector_offsets("tester_1", &offsets).await.unwrap();
+    wait_until_snapshot_done(&client, "tester_1", Duration::from_millis(600)).await.unwrap();
 }
 
+#[test]
+fn test_stack_containers() {
+    let names = stack_containers();
+    assert_eq!(names.len(), 4 + DEBEZIUM_VERSIONS.len() + MYSQL_SERVERS.len());
+    assert_eq!(&names[..3], &[ZOOKEEPER, KAFKA, REGISTRY]);
+    assert!(names.contains(&"db57"));
+}
 
 #[tokio::test]
-async fn create_testbed() {
-    Stack::new_testbed("tester").await;
+async fn test_start_stack() {
+    use crate::stack::runtime::fake::RecordingRuntime;
+
+    let runtime = RecordingRuntime::default();
+    start_stack(&runtime).await.unwrap();
+
+    // everything is created before anything starts, and started in order
+    let calls = runtime.calls();
+    let last_create = calls.iter().rposition(|c| c.starts_with("create ")).unwrap();
+    let first_start = calls.iter().position(|c| c.starts_with("start ")).unwrap();
+    assert!(last_create < first_start);
+    assert_eq!(runtime.calls_of("start"), stack_containers());
+
+    // each worker gets Debezium installed and is restarted before the next container starts
+    for debezium in &DEBEZIUM_VERSIONS {
+        let start = calls.iter().position(|c| *c == format!("start {}", debezium.worker)).unwrap();
+        assert!(calls[start + 1].starts_with(&format!("exec {} bash -c {}", debezium.worker, debezium.install_command())));
+        assert_eq!(calls[start + 2], format!("restart {}", debezium.worker));
+    }
+    assert!(stack_containers().iter().all(|name| runtime.is_running(name)));
+}
+
+#[tokio::test]
+async fn test_start_stack_fails() {
+    use crate::stack::runtime::fake::RecordingRuntime;
+
+    let runtime = RecordingRuntime::default();
+    runtime.fail("start", KAFKA, "port is already allocated");
+    let e = start_stack(&runtime).await.unwrap_err();
+    assert_eq!(e.to_string(), "could not start kafka");
+
+    // nothing after kafka is started, and its output is fetched for the log
+    assert_eq!(runtime.calls_of("start"), vec![ZOOKEEPER, KAFKA]);
+    assert_eq!(runtime.calls_of("logs"), vec![KAFKA]);
+}
+
+#[tokio::test]
+async fn test_configure_debezium_fails() {
+    use crate::stack::containers::kafka_connect::DEBEZIUM_24;
+    use crate::stack::runtime::fake::RecordingRuntime;
+    use crate::stack::runtime::ExecOutput;
+
+    let runtime = RecordingRuntime::default();
+    get_kafka_connect(&runtime, &DEBEZIUM_24).await.unwrap();
+    runtime.start(DEBEZIUM_24.worker).await.unwrap();
+    runtime.exec_output(DEBEZIUM_24.worker, ExecOutput {
+        output: "curl: (6) Could not resolve host".to_string(),
+        exit_code: Some(6),
+    });
+
+    assert!(configure_debezium(&runtime, &DEBEZIUM_24).await.is_err());
+    // the worker isn't restarted without the plugin
+    assert!(runtime.calls_of("restart").is_empty());
+}
+
+#[tokio::test]
+async fn test_stop_and_teardown_stack() {
+    use crate::stack::runtime::fake::RecordingRuntime;
+
+    let runtime = RecordingRuntime::default();
+    start_stack(&runtime).await.unwrap();
+    runtime.fail("stop", KAFKA, "timeout");
+    stop_stack(&runtime).await;
+
+    let mut stopped = stack_containers();
+    stopped.reverse();
+    assert_eq!(runtime.calls_of("stop"), stopped);
+    assert!(runtime.is_running(KAFKA));
+
+    // kafka is still running so it can't be removed, but everything else is
+    teardown_stack(&runtime).await;
+    assert_eq!(runtime.calls_of("remove"), stopped);
+    assert_eq!(runtime.containers(), vec![KAFKA]);
+    assert_eq!(runtime.calls().last().unwrap(), "prune_volumes");
+}
+
+#[tokio::test]
+async fn test_clone_with_dump_fails() {
+    use crate::stack::runtime::fake::RecordingRuntime;
+    use crate::stack::runtime::ExecOutput;
+
+    let runtime = RecordingRuntime::default();
+    get_mysql(&runtime, &DEFAULT_MYSQL_SERVER).await.unwrap();
+    runtime.start(DEFAULT_MYSQL_SERVER.name).await.unwrap();
+    clone_with_dump(&runtime, &DEFAULT_MYSQL_SERVER, "mydb", "tester_1").await.unwrap();
+    assert_eq!(
+        runtime.calls().last().unwrap(),
+        "exec db bash -c set -o pipefail; mysqldump -ptestbed mydb | mysql -ptestbed tester_1"
+    );
+
+    runtime.exec_output(DEFAULT_MYSQL_SERVER.name, ExecOutput {
+        output: "ERROR 1049 (42000): Unknown database 'tester_1'\n".to_string(),
+        exit_code: Some(1),
+    });
+    let e = clone_with_dump(&runtime, &DEFAULT_MYSQL_SERVER, "mydb", "tester_1").await.unwrap_err();
+    assert!(e.to_string().ends_with("Unknown database 'tester_1'"));
 }
 
 
@@ -237,7 +1031,7 @@ async fn list_containers() {
     for c in containers {
         if let Some(ref names) = c.names {
             println!("Names: {names:?}");
-            if names.iter().any(|n| n == MYSQL) {
+            if names.iter().any(|n| MYSQL_SERVERS.iter().any(|s| n == s.name)) {
                 let id = c.id.clone().unwrap();
                 println!("{}", id);
             }
@@ -245,19 +1039,46 @@ async fn list_containers() {
     }
 }
 
-pub async fn clone_database(docker: &Docker, original_db: Option<&str>, new_db: &str) {
+/// Applies the fixtures to the testbed database
+async fn apply_testbed_fixtures(server: &MySqlServer, testbed_name: &str, fixtures: Vec<Fixture>) -> Result<()> {
+    let server = *server;
+    let db_name = testbed_name.to_string();
+    let count = tokio::task::spawn_blocking(move || apply_fixtures(&server, &db_name, &fixtures)).await??;
+    info!("Applied {} fixture rows to: {}", count, testbed_name);
 
-    let source_db = match original_db {
-        Some(db) => db.to_string(),
-        None => DEFAULT_MYSQL_DB.to_string(),
-    };
+    Ok(())
+}
+
+/// Clones the source database into a new database on the server with the specified
+/// strategy, returning how long the clone took
+pub async fn clone_database(runtime: &dyn ContainerRuntime, server: &MySqlServer, source_db: &str, new_db: &str, strategy: CloneStrategy) -> Result<Duration> {
+    info!("Cloning db: {} to: {} on: {} with: {:?}", source_db, new_db, server.name, strategy);
 
-    info!("Cloning db: {} to: {}", source_db, new_db);
-    println!("Cloning db: {} to: {}", source_db, new_db);
+    let start = Instant::now();
 
     info!("Force drop and create: {}", new_db);
-    create_db_forcefully(new_db);
+    let create_server = *server;
+    let db_name = new_db.to_string();
+    tokio::task::spawn_blocking(move || create_db_forcefully(&create_server, &db_name)).await??;
+
+    match strategy {
+        CloneStrategy::Dump => clone_with_dump(runtime, server, source_db, new_db).await?,
+        CloneStrategy::InServer => {
+            let server = *server;
+            let source_db = source_db.to_string();
+            let new_db = new_db.to_string();
+            tokio::task::spawn_blocking(move || clone_in_server(&server, &source_db, &new_db)).await??
+        }
+    }
+
+    let elapsed = start.elapsed();
+    info!("Cloned db: {} with: {:?} in: {:?}", new_db, strategy, elapsed);
+
+    Ok(elapsed)
+}
 
+/// Clones the database by piping `mysqldump` into `mysql` in the server's container
+async fn clone_with_dump(runtime: &dyn ContainerRuntime, server: &MySqlServer, source_db: &str, new_db: &str) -> Result<()> {
     //mysqldump db_name | mysql new_db_name
     //mysqldump -u <user name> --password=<pwd> <original db> | mysql -u <user name> -p <new db>
 
@@ -265,7 +1086,8 @@ pub async fn clone_database(docker: &Docker, original_db: Option<&str>, new_db:
         "bash".to_string(),
         "-c".to_string(),
         format!(
-            "mysqldump -ptestbed {} | mysql -ptestbed {}",
+            // without pipefail a failed dump still exits with mysql's status
+            "set -o pipefail; mysqldump -ptestbed {} | mysql -ptestbed {}",
             source_db, new_db
         ),
     ];
@@ -275,79 +1097,57 @@ pub async fn clone_database(docker: &Docker, original_db: Option<&str>, new_db:
         info!(c);
     }
 
-    // non interactive
-    let exec = docker
-        .create_exec(
-            MYSQL,
-            CreateExecOptions {
-                attach_stdout: Some(true),
-                attach_stderr: Some(true),
-                cmd: Some(cmd),
-                ..Default::default()
-            },
-        )
-        .await
-        .unwrap()
-        .id;
-
-    if let StartExecResults::Attached { mut output, .. } =
-        docker.start_exec(&exec, None).await.unwrap()
-    {
-        while let Some(Ok(msg)) = output.next().await {
-            print!("{msg}");
-        }
-    } else {
-        error!("Could not attach to kafka-connect container");
+    let exec = runtime.exec(server.name, cmd).await?;
+    info!("{}", exec.output);
+
+    if !exec.succeeded() {
+        return Err(anyhow!("could not clone: {} to: {} on: {}: {}", source_db, new_db, server.name, exec.output.trim()));
     }
+
+    Ok(())
 }
 
 #[tokio::test]
 async fn test_clone_db() {
     println!("test_clone_db");
-    let docker = Docker::connect_with_local_defaults().unwrap();
-    clone_database(&docker, None, "foobar").await;
+    let runtime = DockerRuntime::connect().unwrap();
+    clone_database(&runtime, &DEFAULT_MYSQL_SERVER, DEFAULT_TEMPLATE, "foobar", CloneStrategy::Dump).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_clone_db_in_server() {
+    println!("test_clone_db_in_server");
+    let runtime = DockerRuntime::connect().unwrap();
+    clone_database(&runtime, &DEFAULT_MYSQL_SERVER, DEFAULT_TEMPLATE, "foobar", CloneStrategy::InServer).await.unwrap();
 }
 
-pub async fn configure_debezium(docker: &Docker, kafka_connect_id: String) {
-    info!("Configuring debezium");
+/// Installs the Debezium release into its Connect worker and restarts the worker, which only
+/// scans its plugin path on startup
+pub async fn configure_debezium(runtime: &dyn ContainerRuntime, debezium: &DebeziumVersion) -> Result<()> {
+    info!("Configuring debezium {}", debezium.plugin_version);
 
-    let cmd = vec![
-        "bash", 
-        "-c", 
-        "confluent-hub install --no-prompt debezium/debezium-connector-mysql:1.7.0",
-        "confluent-hub install --no-prompt confluentinc/kafka-connect-jdbc:latest",
-        "curl -o /usr/share/confluent-hub-components/confluentinc-kafka-connect-jdbc/lib/mysql.jar https://repo1.maven.org/maven2/mysql/mysql-connector-java/8.0.30/mysql-connector-java-8.0.30.jar",
-        "/etc/confluent/docker/run sleep infinity"
-        ];
+    let install = [
+        debezium.install_command(),
+        "confluent-hub install --no-prompt confluentinc/kafka-connect-jdbc:latest".to_string(),
+        "curl -o /usr/share/confluent-hub-components/confluentinc-kafka-connect-jdbc/lib/mysql.jar https://repo1.maven.org/maven2/mysql/mysql-connector-java/8.0.30/mysql-connector-java-8.0.30.jar".to_string(),
+    ];
 
     info!("Exec commands...");
-    for c in &cmd {
+    for c in &install {
         info!(c);
     }
 
-    // non interactive
-    let exec = docker
-        .create_exec(
-            &kafka_connect_id,
-            CreateExecOptions {
-                attach_stdout: Some(true),
-                attach_stderr: Some(true),
-                cmd: Some(cmd),
-                ..Default::default()
-            },
-        )
-        .await
-        .unwrap()
-        .id;
-
-    if let StartExecResults::Attached { mut output, .. } =
-        docker.start_exec(&exec, None).await.unwrap()
-    {
-        while let Some(Ok(msg)) = output.next().await {
-            print!("{msg}");
-        }
-    } else {
-        error!("Could not attach to kafka-connect container");
+    // bash -c only runs its first argument, so the commands are chained into one script
+    let script = install.join(" && ");
+    let cmd = vec!["bash".to_string(), "-c".to_string(), script];
+
+    let exec = runtime.exec(debezium.worker, cmd).await?;
+    debug!("{}", exec.output);
+
+    if !exec.succeeded() {
+        return Err(anyhow!("could not install debezium {} in: {}", debezium.plugin_version, debezium.worker));
     }
-}
 
+    info!("Restarting {}", debezium.worker);
+    runtime.restart(debezium.worker).await
+}
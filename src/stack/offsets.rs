@@ -0,0 +1,5 @@
+pub use debezium_testbed_client::types::BinlogOffset;
+
+/// The snapshot mode for a connector seeded with offsets: it rebuilds the schema history from the
+/// current schema, then streams from the seeded position instead of snapshotting the data
+pub const RECOVERY_SNAPSHOT_MODE: &str = "schema_only_recovery";
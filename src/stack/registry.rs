@@ -0,0 +1,27 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::stack::TestBed;
+
+/// Every live testbed, by name
+static TESTBEDS: Mutex<BTreeMap<String, TestBed>> = Mutex::new(BTreeMap::new());
+
+pub fn register(testbed: &TestBed) {
+    TESTBEDS
+        .lock()
+        .unwrap()
+        .insert(testbed.name.clone(), testbed.clone());
+}
+
+pub fn get(name: &str) -> Option<TestBed> {
+    TESTBEDS.lock().unwrap().get(name).cloned()
+}
+
+/// Returns every live testbed, sorted by name
+pub fn list() -> Vec<TestBed> {
+    TESTBEDS.lock().unwrap().values().cloned().collect()
+}
+
+pub fn remove(name: &str) -> Option<TestBed> {
+    TESTBEDS.lock().unwrap().remove(name)
+}
@@ -0,0 +1,159 @@
+use anyhow::{anyhow, Result};
+use bollard::container::{Config, CreateContainerOptions, LogsOptions, StartContainerOptions};
+use bollard::exec::{CreateExecOptions, StartExecResults};
+use bollard::image::CreateImageOptions;
+use bollard::network::CreateNetworkOptions;
+use bollard::Docker;
+use futures_util::{StreamExt, TryStreamExt};
+use tracing::info;
+
+use crate::stack::runtime::{ContainerRuntime, ContainerState, ExecOutput};
+
+/// The local Docker daemon
+#[derive(Debug, Clone)]
+pub struct DockerRuntime {
+    docker: Docker,
+}
+
+impl DockerRuntime {
+    /// Connects to the daemon with the local defaults, i.e. `DOCKER_HOST` or the unix socket
+    pub fn connect() -> Result<DockerRuntime> {
+        Ok(DockerRuntime {
+            docker: Docker::connect_with_local_defaults()?,
+        })
+    }
+}
+
+#[rocket::async_trait]
+impl ContainerRuntime for DockerRuntime {
+    async fn create_network(&self, name: &str) -> Result<()> {
+        let config = CreateNetworkOptions {
+            name,
+            check_duplicate: true,
+            internal: false,
+            ..Default::default()
+        };
+
+        self.docker.create_network(config).await?;
+        Ok(())
+    }
+
+    async fn create(&self, name: &str, config: Config<String>) -> Result<String> {
+        let image = config
+            .image
+            .clone()
+            .ok_or_else(|| anyhow!("no image for container: {name}"))?;
+
+        info!("Getting {image} image");
+        self.docker
+            .create_image(
+                Some(CreateImageOptions {
+                    from_image: image,
+                    ..Default::default()
+                }),
+                None,
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let container = self
+            .docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name,
+                    platform: None,
+                }),
+                config,
+            )
+            .await?;
+
+        Ok(container.id)
+    }
+
+    async fn start(&self, name: &str) -> Result<()> {
+        self.docker
+            .start_container(name, None::<StartContainerOptions<String>>)
+            .await?;
+        Ok(())
+    }
+
+    async fn stop(&self, name: &str) -> Result<()> {
+        self.docker.stop_container(name, None).await?;
+        Ok(())
+    }
+
+    async fn restart(&self, name: &str) -> Result<()> {
+        self.docker.restart_container(name, None).await?;
+        Ok(())
+    }
+
+    async fn remove(&self, name: &str) -> Result<()> {
+        self.docker.remove_container(name, None).await?;
+        Ok(())
+    }
+
+    async fn prune_volumes(&self) -> Result<()> {
+        self.docker.prune_volumes::<String>(None).await?;
+        Ok(())
+    }
+
+    async fn exec(&self, name: &str, cmd: Vec<String>) -> Result<ExecOutput> {
+        // non interactive
+        let exec = self
+            .docker
+            .create_exec(
+                name,
+                CreateExecOptions {
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    cmd: Some(cmd),
+                    ..Default::default()
+                },
+            )
+            .await?
+            .id;
+
+        let mut output = String::new();
+        match self.docker.start_exec(&exec, None).await? {
+            StartExecResults::Attached { output: mut stream, .. } => {
+                while let Some(msg) = stream.next().await {
+                    output.push_str(&msg?.to_string());
+                }
+            }
+            StartExecResults::Detached => return Err(anyhow!("could not attach to {name} container")),
+        }
+
+        let exit_code = self.docker.inspect_exec(&exec).await?.exit_code;
+
+        Ok(ExecOutput { output, exit_code })
+    }
+
+    async fn inspect(&self, name: &str) -> Result<ContainerState> {
+        let state = self.docker.inspect_container(name, None).await?.state.unwrap_or_default();
+
+        Ok(ContainerState {
+            running: state.running.unwrap_or(false),
+            status: state.status.map(|s| s.to_string()).unwrap_or_default(),
+            exit_code: state.exit_code,
+        })
+    }
+
+    async fn logs(&self, name: &str, tail: usize) -> Result<String> {
+        let options = LogsOptions {
+            stdout: true,
+            stderr: true,
+            tail: tail.to_string(),
+            ..Default::default()
+        };
+
+        let lines: Vec<String> = self
+            .docker
+            .logs(name, Some(options))
+            .map_ok(|line| line.to_string())
+            .try_collect()
+            .await?;
+
+        Ok(lines.concat())
+    }
+}
@@ -0,0 +1,235 @@
+//! A container runtime that records what it's asked to do instead of running anything, so the
+//! order of the stack's operations and its handling of failures can be tested without Docker:
+//!
+//! ```ignore
+//! let runtime = RecordingRuntime::default();
+//! runtime.fail("start", KAFKA, "port is already allocated");
+//! assert!(start_stack(&runtime).await.is_err());
+//! ```
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use bollard::container::Config;
+
+use crate::stack::runtime::{ContainerRuntime, ContainerState, ExecOutput};
+
+#[derive(Debug, Clone)]
+struct FakeContainer {
+    config: Config<String>,
+    running: bool,
+    logs: String,
+}
+
+/// A failure scripted for the next operation on a container
+#[derive(Debug)]
+struct Failure {
+    op: String,
+    name: String,
+    message: String,
+}
+
+#[derive(Debug, Default)]
+struct RecordingState {
+    calls: Vec<String>,
+    networks: Vec<String>,
+    containers: BTreeMap<String, FakeContainer>,
+    failures: Vec<Failure>,
+    /// The outputs of the next commands run in each container
+    exec_outputs: BTreeMap<String, VecDeque<ExecOutput>>,
+}
+
+impl RecordingState {
+    /// Records the operation and fails it if a failure was scripted for it
+    fn record(&mut self, op: &str, name: &str, call: String) -> Result<()> {
+        self.calls.push(call);
+
+        if let Some(i) = self.failures.iter().position(|f| f.op == op && f.name == name) {
+            let failure = self.failures.remove(i);
+            return Err(anyhow!("{}", failure.message));
+        }
+
+        Ok(())
+    }
+
+    fn container(&mut self, name: &str) -> Result<&mut FakeContainer> {
+        self.containers
+            .get_mut(name)
+            .ok_or_else(|| anyhow!("No such container: {name}"))
+    }
+}
+
+/// Records every operation as `<op> <name>`, i.e. `start kafka`, and `exec <name> <cmd>` for
+/// commands. Containers are created, started and stopped in memory, and every command succeeds
+/// with no output unless told otherwise
+#[derive(Debug, Default)]
+pub struct RecordingRuntime {
+    state: Mutex<RecordingState>,
+}
+
+impl RecordingRuntime {
+    /// Fails the next `op`, named as the trait's method, on the container with the message.
+    /// `prune_volumes` is on no container, so its name is empty
+    pub fn fail(&self, op: &str, name: &str, message: &str) {
+        self.state.lock().unwrap().failures.push(Failure {
+            op: op.to_string(),
+            name: name.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    /// Queues the output of the next command run in the container
+    pub fn exec_output(&self, name: &str, output: ExecOutput) {
+        self.state
+            .lock()
+            .unwrap()
+            .exec_outputs
+            .entry(name.to_string())
+            .or_default()
+            .push_back(output);
+    }
+
+    /// Sets what `logs` returns for the container
+    pub fn set_logs(&self, name: &str, logs: &str) {
+        if let Some(container) = self.state.lock().unwrap().containers.get_mut(name) {
+            container.logs = logs.to_string();
+        }
+    }
+
+    /// Returns every operation so far, in order
+    pub fn calls(&self) -> Vec<String> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Returns the operations so far of one kind, i.e. `start`, as the names they were on
+    pub fn calls_of(&self, op: &str) -> Vec<String> {
+        let prefix = format!("{op} ");
+        self.calls()
+            .iter()
+            .filter_map(|c| c.strip_prefix(&prefix))
+            .map(|name| name.split(' ').next().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    /// Returns the config the container was created with, if it exists
+    pub fn config(&self, name: &str) -> Option<Config<String>> {
+        self.state.lock().unwrap().containers.get(name).map(|c| c.config.clone())
+    }
+
+    pub fn is_running(&self, name: &str) -> bool {
+        self.state.lock().unwrap().containers.get(name).is_some_and(|c| c.running)
+    }
+
+    pub fn containers(&self) -> Vec<String> {
+        self.state.lock().unwrap().containers.keys().cloned().collect()
+    }
+}
+
+#[rocket::async_trait]
+impl ContainerRuntime for RecordingRuntime {
+    async fn create_network(&self, name: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.record("create_network", name, format!("create_network {name}"))?;
+        if state.networks.iter().any(|n| n == name) {
+            return Err(anyhow!("network with name {name} already exists"));
+        }
+        state.networks.push(name.to_string());
+        Ok(())
+    }
+
+    async fn create(&self, name: &str, config: Config<String>) -> Result<String> {
+        let mut state = self.state.lock().unwrap();
+        state.record("create", name, format!("create {name}"))?;
+        if state.containers.contains_key(name) {
+            return Err(anyhow!("Conflict. The container name \"/{name}\" is already in use"));
+        }
+
+        state.containers.insert(
+            name.to_string(),
+            FakeContainer {
+                config,
+                running: false,
+                logs: String::new(),
+            },
+        );
+        Ok(format!("{name}-id"))
+    }
+
+    async fn start(&self, name: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.record("start", name, format!("start {name}"))?;
+        state.container(name)?.running = true;
+        Ok(())
+    }
+
+    async fn stop(&self, name: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.record("stop", name, format!("stop {name}"))?;
+        state.container(name)?.running = false;
+        Ok(())
+    }
+
+    async fn restart(&self, name: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.record("restart", name, format!("restart {name}"))?;
+        state.container(name)?.running = true;
+        Ok(())
+    }
+
+    async fn remove(&self, name: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.record("remove", name, format!("remove {name}"))?;
+        if state.container(name)?.running {
+            return Err(anyhow!("You cannot remove a running container: {name}"));
+        }
+        state.containers.remove(name);
+        Ok(())
+    }
+
+    async fn prune_volumes(&self) -> Result<()> {
+        self.state
+            .lock()
+            .unwrap()
+            .record("prune_volumes", "", "prune_volumes".to_string())
+    }
+
+    async fn exec(&self, name: &str, cmd: Vec<String>) -> Result<ExecOutput> {
+        let mut state = self.state.lock().unwrap();
+        state.record("exec", name, format!("exec {name} {}", cmd.join(" ")))?;
+        if !state.container(name)?.running {
+            return Err(anyhow!("Container {name} is not running"));
+        }
+
+        let output = state
+            .exec_outputs
+            .get_mut(name)
+            .and_then(VecDeque::pop_front)
+            .unwrap_or(ExecOutput {
+                output: String::new(),
+                exit_code: Some(0),
+            });
+        Ok(output)
+    }
+
+    async fn inspect(&self, name: &str) -> Result<ContainerState> {
+        let mut state = self.state.lock().unwrap();
+        state.record("inspect", name, format!("inspect {name}"))?;
+        let running = state.container(name)?.running;
+
+        Ok(ContainerState {
+            running,
+            status: if running { "running" } else { "exited" }.to_string(),
+            exit_code: if running { None } else { Some(0) },
+        })
+    }
+
+    async fn logs(&self, name: &str, tail: usize) -> Result<String> {
+        let mut state = self.state.lock().unwrap();
+        state.record("logs", name, format!("logs {name}"))?;
+        let logs = &state.container(name)?.logs;
+
+        let lines: Vec<&str> = logs.lines().collect();
+        Ok(lines[lines.len().saturating_sub(tail)..].join("\n"))
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use bollard::container::Config;
+
+pub mod docker;
+#[cfg(any(test, feature = "testing"))]
+pub mod fake;
+
+pub use docker::DockerRuntime;
+
+/// The output of a command run in a container
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecOutput {
+    /// Standard output and error, interleaved as the command wrote them
+    pub output: String,
+    /// None when the runtime couldn't tell how the command exited
+    pub exit_code: Option<i64>,
+}
+
+impl ExecOutput {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// The state of a container, as reported by the runtime
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContainerState {
+    pub running: bool,
+    /// i.e. `created`, `running` or `exited`
+    pub status: String,
+    pub exit_code: Option<i64>,
+}
+
+/// What the stack needs from a container runtime. The stack's containers are addressed by name,
+/// which is also their hostname on the `testbed` network
+#[rocket::async_trait]
+pub trait ContainerRuntime: Send + Sync {
+    /// Creates the network, if it doesn't exist
+    async fn create_network(&self, name: &str) -> Result<()>;
+
+    /// Pulls the config's image and creates the container, returning its id
+    async fn create(&self, name: &str, config: Config<String>) -> Result<String>;
+
+    async fn start(&self, name: &str) -> Result<()>;
+
+    async fn stop(&self, name: &str) -> Result<()>;
+
+    async fn restart(&self, name: &str) -> Result<()> {
+        self.stop(name).await?;
+        self.start(name).await
+    }
+
+    async fn remove(&self, name: &str) -> Result<()>;
+
+    /// Removes the volumes no container uses
+    async fn prune_volumes(&self) -> Result<()>;
+
+    /// Runs the command in the container and waits for it to exit
+    async fn exec(&self, name: &str, cmd: Vec<String>) -> Result<ExecOutput>;
+
+    async fn inspect(&self, name: &str) -> Result<ContainerState>;
+
+    /// Returns the last `tail` lines of the container's output
+    async fn logs(&self, name: &str, tail: usize) -> Result<String>;
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use reqwest::header::ACCEPT;
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::info;
+
+pub use debezium_testbed_client::types::{Compatibility, Schema, SubjectInfo};
+
+/// The stack's Schema Registry, as published on the host
+pub const SCHEMA_REGISTRY_URL: &str = "http://localhost:28081";
+
+/// An error from the Schema Registry REST API
+#[derive(Debug)]
+pub enum SchemaRegistryError {
+    /// The request couldn't be sent or the response couldn't be read
+    Http(reqwest::Error),
+    /// The registry responded with an error status
+    Api { status: StatusCode, error_code: i32, message: String },
+}
+
+impl SchemaRegistryError {
+    /// True when the registry has no such subject, version or subject config
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, SchemaRegistryError::Api { status, .. } if *status == StatusCode::NOT_FOUND)
+    }
+}
+
+impl fmt::Display for SchemaRegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaRegistryError::Http(e) => write!(f, "schema registry request failed: {e}"),
+            SchemaRegistryError::Api { status, error_code, message } => {
+                write!(f, "schema registry responded with {status} ({error_code}): {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaRegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SchemaRegistryError::Http(e) => Some(e),
+            SchemaRegistryError::Api { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for SchemaRegistryError {
+    fn from(e: reqwest::Error) -> Self {
+        SchemaRegistryError::Http(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, SchemaRegistryError>;
+
+/// A schema by id, as returned by `/schemas/ids/<id>`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredSchema {
+    /// `AVRO` when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<String>,
+    pub schema: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompatibilityConfig {
+    compatibility_level: Compatibility,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorMessage {
+    error_code: i32,
+    message: String,
+}
+
+/// A client for the Schema Registry REST API
+#[derive(Debug, Clone)]
+pub struct SchemaRegistryClient {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl Default for SchemaRegistryClient {
+    /// A client for the stack's Schema Registry
+    fn default() -> Self {
+        SchemaRegistryClient::new(SCHEMA_REGISTRY_URL)
+    }
+}
+
+impl SchemaRegistryClient {
+    /// Creates a client for the registry at the URL, i.e. `http://localhost:28081`
+    pub fn new(url: &str) -> Self {
+        SchemaRegistryClient {
+            url: url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub async fn subjects(&self) -> Result<Vec<String>> {
+        self.send_json(self.request(Method::GET, "/subjects")).await
+    }
+
+    pub async fn versions(&self, subject: &str) -> Result<Vec<i32>> {
+        self.send_json(self.request(Method::GET, &format!("/subjects/{subject}/versions"))).await
+    }
+
+    /// Returns a version of the subject's schema; `version` is a version number or `latest`
+    pub async fn schema(&self, subject: &str, version: &str) -> Result<Schema> {
+        self.send_json(self.request(Method::GET, &format!("/subjects/{subject}/versions/{version}")))
+            .await
+    }
+
+    /// Returns the schema with the id, as written at the start of every registry encoded message
+    pub async fn schema_by_id(&self, id: u32) -> Result<RegisteredSchema> {
+        self.send_json(self.request(Method::GET, &format!("/schemas/ids/{id}"))).await
+    }
+
+    /// Returns the subject's compatibility level, falling back to the global level
+    pub async fn compatibility(&self, subject: &str) -> Result<Compatibility> {
+        let config: CompatibilityConfig = self
+            .send_json(self.request(Method::GET, &format!("/config/{subject}?defaultToGlobal=true")))
+            .await?;
+        Ok(config.compatibility_level)
+    }
+
+    /// Sets the subject's compatibility level. The subject doesn't need to have any schemas yet
+    pub async fn set_compatibility(&self, subject: &str, compatibility: Compatibility) -> Result<()> {
+        self.send(
+            self.request(Method::PUT, &format!("/config/{subject}"))
+                .json(&json!({ "compatibility": compatibility })),
+        )
+        .await?;
+        info!("Set compatibility of subject {subject} to {compatibility:?}");
+
+        Ok(())
+    }
+
+    /// Removes the subject's compatibility level, so it follows the global level again
+    pub async fn delete_compatibility(&self, subject: &str) -> Result<()> {
+        self.send(self.request(Method::DELETE, &format!("/config/{subject}"))).await?;
+        Ok(())
+    }
+
+    /// Deletes every version of the subject for good; the registry requires a soft delete first
+    pub async fn delete_subject(&self, subject: &str) -> Result<()> {
+        self.send(self.request(Method::DELETE, &format!("/subjects/{subject}"))).await?;
+        self.send(self.request(Method::DELETE, &format!("/subjects/{subject}?permanent=true")))
+            .await?;
+        info!("Deleted subject: {subject}");
+
+        Ok(())
+    }
+
+    /// Returns the subject with its versions and compatibility level
+    pub async fn subject_info(&self, subject: &str) -> Result<SubjectInfo> {
+        Ok(SubjectInfo {
+            subject: subject.to_string(),
+            versions: self.versions(subject).await?,
+            compatibility: self.compatibility(subject).await?,
+        })
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.url, path))
+            .header(ACCEPT, "application/vnd.schemaregistry.v1+json, application/json")
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response> {
+        let response = request.send().await?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let (error_code, message) = match serde_json::from_str::<ErrorMessage>(&body) {
+            Ok(e) => (e.error_code, e.message),
+            Err(_) => (status.as_u16() as i32, body),
+        };
+
+        Err(SchemaRegistryError::Api { status, error_code, message })
+    }
+
+    async fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        Ok(self.send(request).await?.json().await?)
+    }
+}
+
+/// True for the subjects of the testbed's topics under the default `TopicNameStrategy`: its table
+/// topics `<name>.<db>.<table>` and its schema change topic `<name>`
+pub fn is_testbed_subject(subject: &str, testbed_name: &str) -> bool {
+    let topic = subject
+        .strip_suffix("-key")
+        .or_else(|| subject.strip_suffix("-value"));
+
+    match topic {
+        Some(topic) => topic == testbed_name || topic.starts_with(&format!("{testbed_name}.")),
+        None => false,
+    }
+}
+
+#[test]
+fn test_is_testbed_subject() {
+    assert!(is_testbed_subject("demo_1-value", "demo_1"));
+    assert!(is_testbed_subject("demo_1.demo_1.example-key", "demo_1"));
+    assert!(!is_testbed_subject("demo_1.demo_1.example", "demo_1"));
+    assert!(!is_testbed_subject("demo_12.demo_12.example-value", "demo_1"));
+}
+
+#[test]
+fn test_parse_schema() {
+    let schema: Schema = serde_json::from_str(
+        r#"{"subject":"demo_1.demo_1.example-value","version":1,"id":3,"schema":"{\"type\":\"string\"}"}"#,
+    )
+    .unwrap();
+    assert_eq!(schema.schema_type, None);
+
+    let config: CompatibilityConfig = serde_json::from_str(r#"{"compatibilityLevel":"BACKWARD_TRANSITIVE"}"#).unwrap();
+    assert_eq!(config.compatibility_level, Compatibility::BackwardTransitive);
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use mysql::prelude::Queryable;
+use tracing::info;
+
+use crate::stack::containers::mysql::MySqlServer;
+use crate::stack::kafka_connect::KafkaConnectClient;
+use crate::stack::mysql::get_mysql_pool;
+
+/// The range of `database.server.id`s handed to connectors; the MySQL servers themselves use `1`
+pub const FIRST_SERVER_ID: u32 = 5400;
+pub const LAST_SERVER_ID: u32 = 6400;
+
+/// Hands out server ids that are unique among live testbeds and never collide with the id of a
+/// MySQL server. Ids are handed out round robin, so a freed id isn't reused until the rest of the
+/// range has been used
+#[derive(Debug)]
+pub struct ServerIdAllocator {
+    first: u32,
+    last: u32,
+    next: u32,
+    in_use: BTreeSet<u32>,
+    reserved: BTreeSet<u32>,
+    /// The ids of connectors on the stack's workers, which may belong to another process sharing it
+    taken: BTreeSet<u32>,
+}
+
+impl ServerIdAllocator {
+    pub const fn new(first: u32, last: u32) -> Self {
+        ServerIdAllocator {
+            first,
+            last,
+            next: first,
+            in_use: BTreeSet::new(),
+            reserved: BTreeSet::new(),
+            taken: BTreeSet::new(),
+        }
+    }
+
+    /// Marks an id that must never be handed out, i.e. a MySQL server's own id
+    pub fn reserve(&mut self, id: u32) {
+        self.reserved.insert(id);
+    }
+
+    /// Replaces the ids of the connectors on the stack's workers, as last read
+    pub fn set_taken(&mut self, ids: BTreeSet<u32>) {
+        self.taken = ids;
+    }
+
+    /// Returns an id that isn't in use, reserved or taken, or `None` if the range is exhausted
+    pub fn allocate(&mut self) -> Option<u32> {
+        let size = self.last - self.first + 1;
+
+        for _ in 0..size {
+            let id = self.next;
+            self.next = if id == self.last { self.first } else { id + 1 };
+
+            if !self.in_use.contains(&id) && !self.reserved.contains(&id) && !self.taken.contains(&id) {
+                self.in_use.insert(id);
+                return Some(id);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the id to the pool once its testbed is deleted
+    pub fn free(&mut self, id: u32) {
+        self.in_use.remove(&id);
+    }
+
+    pub fn is_in_use(&self, id: u32) -> bool {
+        self.in_use.contains(&id)
+    }
+}
+
+struct ServerIds {
+    allocator: ServerIdAllocator,
+    /// The MySQL servers whose own id has been reserved
+    checked: BTreeSet<&'static str>,
+}
+
+static SERVER_IDS: Mutex<ServerIds> = Mutex::new(ServerIds {
+    allocator: ServerIdAllocator::new(FIRST_SERVER_ID, LAST_SERVER_ID),
+    checked: BTreeSet::new(),
+});
+
+/// Allocates a `database.server.id` for a connector capturing from the server. `taken` are the ids
+/// of every connector on the stack's workers, as another process sharing the stack, i.e. `cargo run`
+/// and a test binary, has its own allocator
+pub fn allocate_server_id(server: &MySqlServer, taken: BTreeSet<u32>) -> Result<u32> {
+    let checked = SERVER_IDS.lock().unwrap().checked.contains(server.name);
+
+    if !checked {
+        let pool = get_mysql_pool(server)?;
+        let own_id: Option<u32> = pool.get_conn()?.query_first("SELECT @@server_id")?;
+
+        let mut ids = SERVER_IDS.lock().unwrap();
+        if let Some(id) = own_id {
+            info!("Reserving server id: {} of: {}", id, server.name);
+            ids.allocator.reserve(id);
+        }
+        ids.checked.insert(server.name);
+    }
+
+    let mut ids = SERVER_IDS.lock().unwrap();
+    ids.allocator.set_taken(taken);
+    let id = ids
+        .allocator
+        .allocate()
+        .ok_or_else(|| anyhow!("no free server ids in {FIRST_SERVER_ID}..={LAST_SERVER_ID}"))?;
+    info!("Allocated server id: {}", id);
+
+    Ok(id)
+}
+
+/// Returns the server ids of the worker's connectors. A worker that can't be reached has none, and
+/// a connector deleted while its config is read is skipped
+pub async fn connector_server_ids(kafka_connect: &KafkaConnectClient) -> BTreeSet<u32> {
+    let mut ids = BTreeSet::new();
+    let Ok(connectors) = kafka_connect.connectors().await else {
+        return ids;
+    };
+
+    for name in connectors {
+        if let Ok(config) = kafka_connect.connector_config(&name).await {
+            ids.extend(config.get("database.server.id").and_then(|id| id.parse::<u32>().ok()));
+        }
+    }
+
+    ids
+}
+
+pub fn free_server_id(id: u32) {
+    info!("Freeing server id: {}", id);
+    SERVER_IDS.lock().unwrap().allocator.free(id);
+}
+
+#[test]
+fn test_allocate_server_ids() {
+    let mut allocator = ServerIdAllocator::new(1, 4);
+    allocator.reserve(1);
+
+    assert_eq!(allocator.allocate(), Some(2));
+    assert_eq!(allocator.allocate(), Some(3));
+    assert_eq!(allocator.allocate(), Some(4));
+    assert_eq!(allocator.allocate(), None);
+
+    // freed ids are reused, live and reserved ids never are
+    allocator.free(3);
+    assert!(!allocator.is_in_use(3));
+    assert_eq!(allocator.allocate(), Some(3));
+    assert_eq!(allocator.allocate(), None);
+}
+
+#[test]
+fn test_freed_ids_are_not_reused_immediately() {
+    let mut allocator = ServerIdAllocator::new(1, 3);
+
+    assert_eq!(allocator.allocate(), Some(1));
+    allocator.free(1);
+    assert_eq!(allocator.allocate(), Some(2));
+    assert_eq!(allocator.allocate(), Some(3));
+    assert_eq!(allocator.allocate(), Some(1));
+}
+
+#[test]
+fn test_taken_ids_are_skipped() {
+    let mut allocator = ServerIdAllocator::new(1, 4);
+    allocator.set_taken(BTreeSet::from([1, 2]));
+    assert_eq!(allocator.allocate(), Some(3));
+
+    // taken ids are replaced on every read, so ids freed by the other process come back
+    allocator.set_taken(BTreeSet::from([4]));
+    assert_eq!(allocator.allocate(), Some(1));
+}
+
+#[tokio::test]
+async fn test_connector_server_ids() {
+    use crate::stack::kafka_connect::fake::FakeKafkaConnect;
+
+    let fake = FakeKafkaConnect::start().await;
+    let client = fake.client();
+    client.put_connector_config("other_1", &serde_json::json!({"database.server.id": "5400"})).await.unwrap();
+    client.put_connector_config("other_2", &serde_json::json!({"database.server.id": "5401"})).await.unwrap();
+    client.put_connector_config("sink", &serde_json::json!({"topics": "demo"})).await.unwrap();
+
+    assert_eq!(connector_server_ids(&client).await, BTreeSet::from([5400, 5401]));
+    assert!(connector_server_ids(&KafkaConnectClient::new("http://127.0.0.1:1")).await.is_empty());
+}
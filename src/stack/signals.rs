@@ -0,0 +1,60 @@
+use anyhow::Result;
+use mysql::prelude::Queryable;
+use serde_json::json;
+use tracing::info;
+
+use crate::stack::connector::ConnectorConfig;
+use crate::stack::containers::mysql::MySqlServer;
+use crate::stack::mysql::get_mysql_pool;
+
+pub use debezium_testbed_client::types::{SnapshotProgress, SnapshotRequest, SnapshotSignal};
+
+/// The table in every testbed database that Debezium reads signals from
+pub const SIGNAL_TABLE: &str = "debezium_signal";
+
+/// Creates the signal table in the testbed database
+pub fn create_signal_table(server: &MySqlServer, db_name: &str) -> Result<()> {
+    let pool = get_mysql_pool(server)?;
+    let mut conn = pool.get_conn()?;
+
+    conn.query_drop(format!(
+        "CREATE TABLE IF NOT EXISTS `{db_name}`.`{SIGNAL_TABLE}` \
+         (id VARCHAR(42) PRIMARY KEY, type VARCHAR(32) NOT NULL, data VARCHAR(2048) NULL)"
+    ))?;
+    info!("Created signal table: {db_name}.{SIGNAL_TABLE}");
+
+    Ok(())
+}
+
+/// Points the connector at the signal table, which it must also capture
+pub fn configure_signals(config: &mut ConnectorConfig, db_name: &str) {
+    let signal_table = format!("{db_name}.{SIGNAL_TABLE}");
+
+    if let Some(list) = &config.table_include_list {
+        if !list.split(',').any(|t| t.trim() == signal_table) {
+            config.table_include_list = Some(format!("{list},{signal_table}"));
+        }
+    }
+    config.set("signal.data.collection", signal_table);
+}
+
+/// Writes an `execute-snapshot` signal for the tables, returning the signal id and the
+/// qualified table names
+pub fn signal_snapshot(server: &MySqlServer, db_name: &str, tables: &[String]) -> Result<(String, Vec<String>)> {
+    let tables: Vec<String> = tables
+        .iter()
+        .map(|t| if t.contains('.') { t.clone() } else { format!("{db_name}.{t}") })
+        .collect();
+    let id = format!("{:016x}", rand::random::<u64>());
+    let data = json!({ "data-collections": tables, "type": "incremental" }).to_string();
+
+    let pool = get_mysql_pool(server)?;
+    let mut conn = pool.get_conn()?;
+    conn.exec_drop(
+        format!("INSERT INTO `{db_name}`.`{SIGNAL_TABLE}` (id, type, data) VALUES (?, 'execute-snapshot', ?)"),
+        (&id, &data),
+    )?;
+    info!("Signalled incremental snapshot {id} of: {tables:?}");
+
+    Ok((id, tables))
+}
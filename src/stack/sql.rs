@@ -0,0 +1,108 @@
+use anyhow::Result;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use mysql::consts::ColumnType;
+use mysql::prelude::Queryable;
+use mysql::{Column, Pool, Value};
+use serde_json::json;
+
+use crate::stack::containers::mysql::MySqlServer;
+use crate::stack::error::TestBedError;
+use crate::stack::mysql::get_mysql_url;
+
+pub use debezium_testbed_client::types::{SqlRequest, SqlResult};
+
+/// Runs one or more statements against the database. Errors reported by the server, such as a
+/// syntax error or an unknown table, are an `InvalidSql`
+pub fn execute_sql(server: &MySqlServer, db_name: &str, sql: &str) -> Result<SqlResult> {
+    let pool = Pool::new(get_mysql_url(server, db_name).as_str())?;
+    let mut conn = pool.get_conn()?;
+
+    let mut result = SqlResult::default();
+    let mut query_result = conn.query_iter(sql).map_err(sql_error)?;
+    while let Some(mut set) = query_result.iter() {
+        let columns: Vec<Column> = set.columns().as_ref().to_vec();
+        let rows = set
+            .by_ref()
+            .map(|row| {
+                row.map(|row| {
+                    row.unwrap()
+                        .into_iter()
+                        .zip(&columns)
+                        .map(|(value, column)| json_value(value, column))
+                        .collect()
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_error)?;
+
+        if !columns.is_empty() {
+            result.columns = columns.iter().map(|c| c.name_str().to_string()).collect();
+            result.rows = rows;
+        }
+        result.affected_rows += set.affected_rows();
+        if let Some(id) = set.last_insert_id().filter(|id| *id != 0) {
+            result.last_insert_id = Some(id);
+        }
+    }
+
+    Ok(result)
+}
+
+fn sql_error(e: mysql::Error) -> anyhow::Error {
+    match e {
+        mysql::Error::MySqlError(e) => TestBedError::InvalidSql(e.message).into(),
+        e => e.into(),
+    }
+}
+
+/// Converts a value read over the text protocol to JSON. Integers and floats become numbers,
+/// binary values that aren't UTF-8 become base64, and everything else, including decimals, a string
+fn json_value(value: Value, column: &Column) -> serde_json::Value {
+    let text = match value {
+        Value::NULL => return serde_json::Value::Null,
+        Value::Bytes(bytes) => match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => return json!(BASE64_STANDARD.encode(e.into_bytes())),
+        },
+        Value::Int(n) => return json!(n),
+        Value::UInt(n) => return json!(n),
+        Value::Float(n) => return json!(n),
+        Value::Double(n) => return json!(n),
+        other => other.as_sql(true).trim_matches('\'').to_string(),
+    };
+
+    let number = match column.column_type() {
+        ColumnType::MYSQL_TYPE_TINY
+        | ColumnType::MYSQL_TYPE_SHORT
+        | ColumnType::MYSQL_TYPE_INT24
+        | ColumnType::MYSQL_TYPE_LONG
+        | ColumnType::MYSQL_TYPE_LONGLONG
+        | ColumnType::MYSQL_TYPE_YEAR => text
+            .parse::<i64>()
+            .map(|n| json!(n))
+            .or_else(|_| text.parse::<u64>().map(|n| json!(n)))
+            .ok(),
+        ColumnType::MYSQL_TYPE_FLOAT | ColumnType::MYSQL_TYPE_DOUBLE => text.parse::<f64>().ok().map(|n| json!(n)),
+        _ => None,
+    };
+
+    number.unwrap_or(serde_json::Value::String(text))
+}
+
+#[test]
+fn test_json_value() {
+    let column = |column_type| Column::new(column_type);
+
+    assert_eq!(json_value(Value::Bytes(b"42".to_vec()), &column(ColumnType::MYSQL_TYPE_LONG)), json!(42));
+    assert_eq!(
+        json_value(Value::Bytes(b"18446744073709551615".to_vec()), &column(ColumnType::MYSQL_TYPE_LONGLONG)),
+        json!(u64::MAX)
+    );
+    assert_eq!(json_value(Value::Bytes(b"1.5".to_vec()), &column(ColumnType::MYSQL_TYPE_DOUBLE)), json!(1.5));
+    assert_eq!(
+        json_value(Value::Bytes(b"10.20".to_vec()), &column(ColumnType::MYSQL_TYPE_NEWDECIMAL)),
+        json!("10.20")
+    );
+    assert_eq!(json_value(Value::Bytes(vec![0xff, 0x00]), &column(ColumnType::MYSQL_TYPE_BLOB)), json!("/wA="));
+    assert_eq!(json_value(Value::NULL, &column(ColumnType::MYSQL_TYPE_VAR_STRING)), serde_json::Value::Null);
+}
@@ -0,0 +1,71 @@
+use std::{env, fs, path::PathBuf};
+
+use crate::stack::error::TestBedError;
+
+/// The template used when a testbed request doesn't pick one
+pub const DEFAULT_TEMPLATE: &str = "mydb";
+
+/// A template database. Each subdirectory of `templates/` is a template holding:
+///
+/// - `init.sql`, which creates and fills a database named after the directory
+/// - `connector.json`, the Debezium connector for that database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    /// The name of the directory, which is also the name of the template database
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+impl Template {
+    pub fn init_sql(&self) -> PathBuf {
+        self.dir.join("init.sql")
+    }
+
+    pub fn connector_json(&self) -> PathBuf {
+        self.dir.join("connector.json")
+    }
+}
+
+pub fn get_templates_dir() -> PathBuf {
+    let path = env::current_dir().unwrap();
+    path.join("templates")
+}
+
+/// Returns every template in the `templates` directory, sorted by name
+pub fn get_templates() -> Vec<Template> {
+    let mut templates: Vec<Template> = fs::read_dir(get_templates_dir())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("init.sql").is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            is_valid_name(&name).then(|| Template {
+                name,
+                dir: entry.path(),
+            })
+        })
+        .collect();
+
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+/// Returns the template with the specified name
+pub fn get_template(name: &str) -> Result<Template, TestBedError> {
+    get_templates()
+        .into_iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| TestBedError::UnknownTemplate(name.to_string()))
+}
+
+/// Template names are used as database names, so only letters, digits and underscores are allowed
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[test]
+fn test_get_templates() {
+    let templates = get_templates();
+    assert!(templates.iter().any(|t| t.name == DEFAULT_TEMPLATE));
+    assert!(templates.iter().all(|t| t.connector_json().is_file()));
+}
@@ -0,0 +1,177 @@
+use crate::stack::connector::ConnectorConfig;
+
+pub use debezium_testbed_client::types::{TopicRoute, Transform};
+
+const ROUTER_CLASS: &str = "io.debezium.transforms.ByLogicalTableRouter";
+
+/// Returns the properties of the transform, relative to `transforms.<alias>.`
+fn transform_properties(transform: &Transform, testbed_name: &str) -> Vec<(&'static str, String)> {
+    match transform {
+        Transform::Unwrap { drop_tombstones, delete_handling, add_fields } => {
+            let mut properties = vec![
+                ("type", "io.debezium.transforms.ExtractNewRecordState".to_string()),
+                ("drop.tombstones", drop_tombstones.to_string()),
+            ];
+            if let Some(mode) = delete_handling {
+                properties.push(("delete.handling.mode", mode.clone()));
+            }
+            if !add_fields.is_empty() {
+                properties.push(("add.fields", add_fields.join(",")));
+            }
+            properties
+        }
+        Transform::Reroute { topic_regex, topic_replacement } => vec![
+            ("type", ROUTER_CLASS.to_string()),
+            ("topic.regex", topic_regex.clone()),
+            ("topic.replacement", topic_replacement.clone()),
+        ],
+        Transform::Filter { keep, .. } => vec![
+            ("type", "org.apache.kafka.connect.transforms.Filter".to_string()),
+            ("negate", keep.to_string()),
+        ],
+        Transform::Header { header, value } => vec![
+            ("type", "org.apache.kafka.connect.transforms.InsertHeader".to_string()),
+            ("header", header.clone()),
+            ("value.literal", value.clone().unwrap_or_else(|| testbed_name.to_string())),
+        ],
+        Transform::Mask { fields, replacement } => {
+            let mut properties = vec![
+                ("type", "org.apache.kafka.connect.transforms.MaskField$Value".to_string()),
+                ("fields", fields.join(",")),
+            ];
+            if let Some(replacement) = replacement {
+                properties.push(("replacement", replacement.clone()));
+            }
+            properties
+        }
+    }
+}
+
+/// Appends the transforms to the connector config. Each transform is aliased by its preset name,
+/// with a number appended when a preset is stacked more than once
+pub fn apply_transforms(config: &mut ConnectorConfig, transforms: &[Transform], testbed_name: &str) {
+    if transforms.is_empty() {
+        return;
+    }
+
+    let mut aliases: Vec<String> = list(config.get("transforms"));
+    let mut predicates: Vec<String> = list(config.get("predicates"));
+
+    for transform in transforms {
+        let alias = unique_alias(transform.name(), &aliases);
+
+        for (name, value) in transform_properties(transform, testbed_name) {
+            config.set(&format!("transforms.{alias}.{name}"), value);
+        }
+
+        // filters drop whatever their predicate matches
+        if let Transform::Filter { topic_regex, .. } = transform {
+            let predicate = unique_alias(&format!("{alias}_topic"), &predicates);
+            config.set(&format!("transforms.{alias}.predicate"), predicate.clone());
+            config.set(
+                &format!("predicates.{predicate}.type"),
+                "org.apache.kafka.connect.transforms.predicates.TopicNameMatches",
+            );
+            config.set(&format!("predicates.{predicate}.pattern"), topic_regex.clone());
+            predicates.push(predicate);
+        }
+
+        aliases.push(alias);
+    }
+
+    config.set("transforms", aliases.join(","));
+    if !predicates.is_empty() {
+        config.set("predicates", predicates.join(","));
+    }
+}
+
+/// Returns the connector's `ByLogicalTableRouter` transforms, from the template or the presets, in
+/// the order they're applied
+pub fn topic_routes(config: &ConnectorConfig) -> Vec<TopicRoute> {
+    list(config.get("transforms"))
+        .into_iter()
+        .filter(|alias| config.get(&format!("transforms.{alias}.type")) == Some(ROUTER_CLASS))
+        .filter_map(|alias| {
+            Some(TopicRoute {
+                regex: config.get(&format!("transforms.{alias}.topic.regex"))?.to_string(),
+                replacement: config.get(&format!("transforms.{alias}.topic.replacement"))?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn unique_alias(name: &str, taken: &[String]) -> String {
+    let mut alias = name.to_string();
+    let mut n = 1;
+    while taken.contains(&alias) {
+        n += 1;
+        alias = format!("{name}{n}");
+    }
+    alias
+}
+
+#[test]
+fn test_apply_transforms() {
+    let mut config: ConnectorConfig = serde_json::from_value(serde_json::json!({
+        "connector.class": "io.debezium.connector.mysql.MySqlConnector",
+        "database.hostname": "db",
+        "database.server.id": "1",
+        "transforms": "unwrap",
+        "transforms.unwrap.type": "io.debezium.transforms.ExtractNewRecordState"
+    }))
+    .unwrap();
+
+    let transforms: Vec<Transform> = serde_json::from_value(serde_json::json!([
+        { "type": "unwrap", "drop_tombstones": true, "add_fields": ["op", "source.ts_ms"] },
+        { "type": "filter", "topic_regex": ".*\\.audit_log" },
+        { "type": "header", "header": "testbed" }
+    ]))
+    .unwrap();
+
+    apply_transforms(&mut config, &transforms, "demo_1");
+
+    assert_eq!(config.get("transforms"), Some("unwrap,unwrap2,filter,header"));
+    assert_eq!(config.get("transforms.unwrap2.drop.tombstones"), Some("true"));
+    assert_eq!(config.get("transforms.unwrap2.add.fields"), Some("op,source.ts_ms"));
+    assert_eq!(config.get("transforms.filter.predicate"), Some("filter_topic"));
+    assert_eq!(config.get("predicates"), Some("filter_topic"));
+    assert_eq!(config.get("predicates.filter_topic.pattern"), Some(".*\\.audit_log"));
+    assert_eq!(config.get("transforms.header.value.literal"), Some("demo_1"));
+}
+
+#[test]
+fn test_topic_routes() {
+    let mut config: ConnectorConfig = serde_json::from_value(serde_json::json!({
+        "connector.class": "io.debezium.connector.mysql.MySqlConnector",
+        "database.hostname": "db",
+        "database.server.id": "1"
+    }))
+    .unwrap();
+
+    let transforms: Vec<Transform> = serde_json::from_value(serde_json::json!([
+        { "type": "unwrap" },
+        { "type": "reroute", "topic_regex": "demo_1\\.demo_1\\.(.*)", "topic_replacement": "demo_1.all" }
+    ]))
+    .unwrap();
+    apply_transforms(&mut config, &transforms, "demo_1");
+
+    let routes = topic_routes(&config);
+    assert_eq!(
+        routes,
+        vec![TopicRoute {
+            regex: "demo_1\\.demo_1\\.(.*)".to_string(),
+            replacement: "demo_1.all".to_string(),
+        }]
+    );
+    assert_eq!(debezium_testbed_client::types::table_topic("demo_1", "orders", &routes), "demo_1.all");
+}
@@ -0,0 +1,17 @@
+CREATE DATABASE billing;
+USE billing
+
+CREATE TABLE invoices (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    account VARCHAR(64) NOT NULL,
+    amount DECIMAL(12, 2) NOT NULL,
+    due_date DATE NOT NULL
+);
+
+CREATE TABLE payments (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    invoice_id INT NOT NULL,
+    amount DECIMAL(12, 2) NOT NULL,
+    paid_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (invoice_id) REFERENCES invoices (id)
+);